@@ -1,9 +1,21 @@
 mod structure;
 mod tool;
 
+use crate::structure::arena_bst::ArenaBst;
+use crate::structure::augmented_bst::{AugNode, AugNodeLink, Augmentation, Min, Size, Sum};
 use crate::structure::bst::BstNode;
+use crate::structure::bst::DeleteStrategy;
+use crate::structure::interval_bst::{IntervalNode, IntervalNodeLink};
+use crate::structure::mapped_bst::MappedBst;
+use crate::structure::persistent_bst::PersistentBst;
+use crate::structure::sync_bst::SyncBst;
+use crate::structure::tree_codec::CodecError;
+use crate::structure::tree_notation::NotationError;
+use crate::structure::tree_rebuild::TraversalError;
+use crate::structure::tree_traversal::PositionedNode;
 use crate::structure::tree::Node; 
 use crate::structure::tree::NodeLink;
+use crate::structure::tree::LevelOrderError;
 use crate::structure::bst::BstNodeLink;
 use crate::tool::generate_dotfile; 
 use crate::tool::generate_dotfile_bst;
@@ -14,6 +26,48 @@ use std::rc::Rc;
 
 fn main() {
     test_binary_search_tree();
+    test_bst_bulk_removal();
+    test_bst_remove_by_key();
+    test_bst_delete_strategy();
+    test_bst_size_queries();
+    test_bst_path_queries();
+    test_arena_bst();
+    test_sync_bst();
+    test_persistent_bst();
+    test_deep_clone();
+    test_tree_equality();
+    test_interval_tree();
+    test_augmented_bst();
+    test_bst_serde();
+    test_binary_format();
+    test_mapped_bst();
+    test_node_lookup();
+    test_node_discard();
+    test_level_order();
+    test_tree_rebuild();
+    test_tree_notation();
+    test_tree_bst_conversion();
+    test_tree_shape();
+    test_tree_compare();
+    test_tree_paths();
+    test_tree_views();
+}
+
+//helper to build a bst from a list of keys, in the given insertion order
+fn build_bst(keys: &[i32]) -> BstNodeLink {
+    let mut rootlink: Option<BstNodeLink> = None;
+    for &key in keys {
+        rootlink = Some(BstNode::tree_insert(rootlink, key));
+    }
+    rootlink.expect("keys should not be empty")
+}
+
+//helper to list the keys of a bst in order
+fn bst_keys(root: &BstNodeLink) -> Vec<i32> {
+    BstNode::inorder_nodelinks(root)
+        .iter()
+        .map(|node| node.borrow().key.unwrap())
+        .collect()
 }
 
 fn test_binary_search_tree(){
@@ -37,7 +91,7 @@ fn test_binary_search_tree(){
     generate_dotfile_bst(&current_root, main_tree_path_initial);
 
     println!("\n--- Tree Search Tests ---");
-    let search_keys = vec![15, 9, 22, 4, 100];
+    let search_keys = [15, 9, 22, 4, 100];
 
     for &key in search_keys.iter() {
         print!("tree search result of key {} is ", key);
@@ -189,6 +243,1101 @@ fn test_binary_search_tree(){
 
 }
 
+fn test_bst_bulk_removal() {
+    println!("\n--- Bulk Removal Tests ---");
+    let keys = [15, 6, 18, 17, 20, 3, 7, 2, 4, 13, 9];
+
+    // extract every odd key, including the root
+    let (new_root, removed) = BstNode::extract_if(Some(build_bst(&keys)), |k| k % 2 == 1);
+    println!("extract_if odd keys removed {:?}", removed);
+    let new_root = new_root.expect("even keys should be left");
+    println!("keys left after extract_if {:?}", bst_keys(&new_root));
+    assert_eq!(removed, vec![3, 7, 9, 13, 15, 17]);
+    assert_eq!(bst_keys(&new_root), vec![2, 4, 6, 18, 20]);
+    assert!(new_root.borrow().parent.is_none());
+    for node in BstNode::inorder_nodelinks(&new_root) {
+        // every child must point back to the node holding it
+        for child in [node.borrow().left.clone(), node.borrow().right.clone()].into_iter().flatten() {
+            let parent = child.borrow().parent.as_ref().and_then(|p| p.upgrade());
+            assert!(parent.is_some_and(|p| Rc::ptr_eq(&p, &node)));
+        }
+    }
+
+    let retained = BstNode::retain(Some(build_bst(&keys)), |k| *k > 10);
+    let retained = retained.expect("keys above 10 should be left");
+    println!("retain keys > 10 {:?}", bst_keys(&retained));
+    assert_eq!(bst_keys(&retained), vec![13, 15, 17, 18, 20]);
+    let emptied = BstNode::retain(Some(build_bst(&keys)), |_| false);
+    assert!(emptied.is_none());
+    // an emptied tree can be passed straight to the next call
+    let (still_empty, removed) = BstNode::extract_if(emptied.clone(), |_| true);
+    assert!(still_empty.is_none() && removed.is_empty());
+    assert!(BstNode::retain(emptied.clone(), |_| true).is_none());
+    assert!(BstNode::drain(emptied).is_empty());
+
+    let drained = BstNode::drain(Some(build_bst(&keys)));
+    println!("drain result {:?}", drained);
+    assert_eq!(drained, vec![2, 3, 4, 6, 7, 9, 13, 15, 17, 18, 20]);
+}
+
+fn test_bst_remove_by_key() {
+    println!("\n--- Remove By Key Tests ---");
+    let keys = [15, 6, 18, 17, 20, 3, 7, 2, 4, 13, 9];
+
+    let (rootlink, removed) = BstNode::remove(Some(build_bst(&keys)), &6);
+    println!("remove 6 returned {:?}", removed);
+    assert_eq!(removed, Some(6));
+    let (rootlink, removed) = BstNode::remove(rootlink, &99);
+    println!("remove 99 returned {:?}", removed);
+    assert_eq!(removed, None);
+    let (rootlink, removed) = BstNode::remove(rootlink, &15);
+    println!("remove 15 (root) returned {:?}", removed);
+    assert_eq!(removed, Some(15));
+    let rootlink = rootlink.expect("tree should not be empty");
+    println!("keys after removals {:?}", bst_keys(&rootlink));
+    assert_eq!(bst_keys(&rootlink), vec![2, 3, 4, 7, 9, 13, 17, 18, 20]);
+
+    let (rootlink, first) = BstNode::pop_first(Some(rootlink));
+    let (rootlink, last) = BstNode::pop_last(rootlink);
+    println!("pop_first {:?}, pop_last {:?}", first, last);
+    assert_eq!((first, last), (Some(2), Some(20)));
+
+    // popping until empty should return every key in ascending order
+    let mut rootlink = rootlink;
+    let mut popped = Vec::new();
+    while let (new_root, Some(key)) = BstNode::pop_first(rootlink.clone()) {
+        popped.push(key);
+        rootlink = new_root;
+    }
+    assert_eq!(popped, vec![3, 4, 7, 9, 13, 17, 18]);
+    assert!(rootlink.is_none());
+
+    // deleting a node that belongs to another tree must leave both trees untouched
+    let tree_a = build_bst(&[10, 5, 15]);
+    let tree_b = build_bst(&[10, 5, 15]);
+    let foreign = BstNode::tree_search(Rc::clone(&tree_b), &5).unwrap();
+    let result = BstNode::try_tree_delete(Rc::clone(&tree_a), Rc::clone(&foreign));
+    let foreign_error = result.expect_err("a node of another tree must be rejected");
+    println!("{}", foreign_error);
+    assert_eq!(foreign_error.key, Some(5));
+    assert!(Rc::ptr_eq(&foreign_error.root, &tree_a));
+    let tree_a = BstNode::tree_delete(tree_a, foreign);
+    assert_eq!(bst_keys(&tree_a), vec![5, 10, 15]);
+    assert_eq!(bst_keys(&tree_b), vec![5, 10, 15]);
+    // a node of the tree itself is deleted, even when the root stays the same
+    let own = BstNode::tree_search(Rc::clone(&tree_a), &5).unwrap();
+    let tree_a = BstNode::try_tree_delete(tree_a, own).expect("5 belongs to tree_a");
+    assert_eq!(bst_keys(&tree_a), vec![10, 15]);
+
+    // deep nodes of a degenerate tree are deleted without walking up recursively
+    let chain_len = 100_000;
+    let chain_root = BstNode::new_bst_nodelink(0);
+    let mut last = Rc::clone(&chain_root);
+    for key in 1..chain_len {
+        last.borrow_mut().add_right_child(&last, key);
+        let next = last.borrow().right.clone().unwrap();
+        last = next;
+    }
+    assert!(Rc::ptr_eq(&BstNode::get_root(&last), &chain_root));
+    let (chain_root, last_key) = BstNode::pop_last(Some(chain_root));
+    assert_eq!(last_key, Some(chain_len - 1));
+    let (chain_root, removed) = BstNode::remove(chain_root, &(chain_len - 2));
+    assert_eq!(removed, Some(chain_len - 2));
+    let chain_root = chain_root.unwrap();
+    let deep = BstNode::tree_search(Rc::clone(&chain_root), &(chain_len - 3)).unwrap();
+    let chain_root = BstNode::try_tree_delete(chain_root, deep).expect("deep node belongs to the chain");
+    assert_eq!(BstNode::size(&chain_root), chain_len as usize - 3);
+}
+
+fn test_bst_delete_strategy() {
+    println!("\n--- Delete Strategy Tests ---");
+    let keys = [15, 6, 18, 17, 20, 3, 7, 2, 4, 13, 9];
+
+    // 15 has two children: the successor 17 or the predecessor 13 takes its place
+    let (rootlink, _) = BstNode::remove_with(Some(build_bst(&keys)), &15, &mut DeleteStrategy::Successor);
+    println!("root after deleting 15 by successor {:?}", rootlink.as_ref().unwrap().borrow().key);
+    assert_eq!(rootlink.unwrap().borrow().key, Some(17));
+    let (rootlink, _) = BstNode::remove_with(Some(build_bst(&keys)), &15, &mut DeleteStrategy::Predecessor);
+    println!("root after deleting 15 by predecessor {:?}", rootlink.as_ref().unwrap().borrow().key);
+    let rootlink = rootlink.unwrap();
+    assert_eq!(rootlink.borrow().key, Some(13));
+    assert_eq!(bst_keys(&rootlink), vec![2, 3, 4, 6, 7, 9, 13, 17, 18, 20]);
+    assert!(rootlink.borrow().parent.is_none());
+
+    // the same through a node handle, 6 is replaced by its predecessor 4
+    let node_6 = BstNode::tree_search(Rc::clone(&rootlink), &6).unwrap();
+    let rootlink = BstNode::tree_delete_with(rootlink, node_6, &mut DeleteStrategy::Predecessor);
+    assert_eq!(rootlink.borrow().left.as_ref().unwrap().borrow().key, Some(4));
+
+    // alternating flips between successor and predecessor on each two children deletion
+    let mut strategy = DeleteStrategy::alternating();
+    let (rootlink, _) = BstNode::remove_with(Some(build_bst(&keys)), &15, &mut strategy);
+    let (rootlink, _) = BstNode::remove_with(rootlink, &17, &mut strategy);
+    println!("root after alternating deletes of 15 and 17 {:?}", rootlink.as_ref().unwrap().borrow().key);
+    assert_eq!(rootlink.unwrap().borrow().key, Some(13));
+
+    // long delete/insert cycles must keep every key with any strategy
+    for mut strategy in [DeleteStrategy::Predecessor, DeleteStrategy::alternating(), DeleteStrategy::random(7)] {
+        let mut rootlink = Some(build_bst(&keys));
+        for round in 0..50 {
+            let key = keys[round % keys.len()];
+            rootlink = BstNode::remove_with(rootlink, &key, &mut strategy).0;
+            rootlink = Some(BstNode::tree_insert(rootlink, key));
+        }
+        let rootlink = rootlink.unwrap();
+        assert_eq!(bst_keys(&rootlink), vec![2, 3, 4, 6, 7, 9, 13, 15, 17, 18, 20]);
+        println!("{:?} keeps all keys after 50 cycles", strategy);
+    }
+}
+
+fn test_bst_size_queries() {
+    println!("\n--- Height/Depth/Size Tests ---");
+    let rootlink = build_bst(&[15, 6, 18, 17, 20, 3, 7, 2, 4, 13, 9]);
+    println!("height {}", BstNode::height(&rootlink));
+    println!("size {}", BstNode::size(&rootlink));
+    println!("level widths {:?}", BstNode::level_widths(&rootlink));
+    assert_eq!(BstNode::height(&rootlink), 4);
+    assert_eq!(BstNode::size(&rootlink), 11);
+    assert_eq!(BstNode::level_widths(&rootlink), vec![1, 2, 4, 3, 1]);
+
+    let node_9 = BstNode::tree_search(Rc::clone(&rootlink), &9).unwrap();
+    println!("depth of 9 is {}", BstNode::depth(&node_9));
+    assert_eq!(BstNode::depth(&node_9), 4);
+    assert_eq!(BstNode::depth(&rootlink), 0);
+    assert!(node_9.borrow().is_leaf());
+    assert!(!rootlink.borrow().is_leaf());
+
+    // degenerate tree, every key on the right, built directly since tree_insert is quadratic here
+    let chain_len = 100_000;
+    let chain_root = BstNode::new_bst_nodelink(0);
+    let mut last = Rc::clone(&chain_root);
+    for key in 1..chain_len {
+        last.borrow_mut().add_right_child(&last, key);
+        let next = last.borrow().right.clone().unwrap();
+        last = next;
+    }
+    println!("degenerate tree height {}, size {}, depth of last {}",
+        BstNode::height(&chain_root), BstNode::size(&chain_root), BstNode::depth(&last));
+    assert_eq!(BstNode::height(&chain_root), chain_len as usize - 1);
+    assert_eq!(BstNode::size(&chain_root), chain_len as usize);
+    assert_eq!(BstNode::depth(&last), chain_len as usize - 1);
+}
+
+fn test_bst_path_queries() {
+    println!("\n--- LCA/Path Tests ---");
+    let rootlink = build_bst(&[15, 6, 18, 17, 20, 3, 7, 2, 4, 13, 9]);
+    let keys_of = |path: Vec<BstNodeLink>| -> Vec<i32> {
+        path.iter().map(|node| node.borrow().key.unwrap()).collect()
+    };
+
+    let lca_cases = [(2, 4, Some(3)), (4, 9, Some(6)), (9, 17, Some(15)), (13, 9, Some(13)), (2, 99, None)];
+    for (a, b, expected) in lca_cases {
+        let ancestor = BstNode::lca(&rootlink, &a, &b).map(|node| node.borrow().key.unwrap());
+        println!("lca of {} and {} is {:?}", a, b, ancestor);
+        assert_eq!(ancestor, expected);
+    }
+
+    let node_9 = BstNode::tree_search(Rc::clone(&rootlink), &9).unwrap();
+    let path = keys_of(BstNode::path_from_root(&node_9));
+    println!("path from root to 9 {:?}", path);
+    assert_eq!(path, vec![15, 6, 7, 13, 9]);
+
+    let path = keys_of(BstNode::path_between(&rootlink, &4, &9).unwrap());
+    println!("path between 4 and 9 {:?}", path);
+    assert_eq!(path, vec![4, 3, 6, 7, 13, 9]);
+
+    assert_eq!(BstNode::distance(&rootlink, &4, &9), Some(5));
+    assert_eq!(BstNode::distance(&rootlink, &2, &20), Some(5));
+    assert_eq!(BstNode::distance(&rootlink, &7, &7), Some(0));
+    assert_eq!(BstNode::distance(&rootlink, &7, &99), None);
+    println!("distance between 2 and 20 is {:?}", BstNode::distance(&rootlink, &2, &20));
+}
+
+fn test_arena_bst() {
+    println!("\n--- Arena BST Tests ---");
+    let keys = [15, 6, 18, 17, 20, 3, 7, 2, 4, 13, 9];
+    let mut arena = ArenaBst::with_capacity(keys.len());
+    for key in keys {
+        arena.tree_insert(key);
+    }
+    assert_eq!(arena.tree_insert(15), None);
+    println!("arena keys {:?}", arena.inorder_keys());
+    assert_eq!(arena.len(), keys.len());
+
+    let node_13 = arena.tree_search(&13).expect("13 should be in the arena");
+    let successor = arena.tree_successor(node_13).and_then(|index| arena.key(index));
+    println!("successor of 13 is {:?}", successor);
+    assert_eq!(successor, Some(15));
+    assert_eq!(arena.tree_search(&22), None);
+    let root = arena.root().unwrap();
+    let (min, max) = (arena.key(arena.minimum(root)), arena.key(arena.maximum(root)));
+    println!("arena minimum {:?}, maximum {:?}", min, max);
+    assert_eq!((min, max), (Some(2), Some(20)));
+
+    // delete a leaf, a node with one child, a node with two children and the root
+    for key in [4, 18, 6, 15] {
+        assert_eq!(arena.remove(&key), Some(key));
+    }
+    println!("arena keys after deletes {:?}", arena.inorder_keys());
+    assert_eq!(arena.inorder_keys(), vec![2, 3, 7, 9, 13, 17, 20]);
+    let root = arena.root().unwrap();
+    assert_eq!(arena.parent(root), None);
+    let mut stack = vec![root];
+    while let Some(index) = stack.pop() {
+        // every child must point back to its parent
+        for child in [arena.left(index), arena.right(index)].into_iter().flatten() {
+            assert_eq!(arena.parent(child), Some(index));
+            stack.push(child);
+        }
+    }
+
+    // the freed slots are reused before the arena grows
+    let reused = arena.tree_insert(5).unwrap();
+    println!("key 5 reused slot {}", reused);
+    assert!((reused as usize) < keys.len());
+
+    while let Some(root) = arena.root() {
+        arena.tree_delete(root);
+    }
+    assert!(arena.is_empty());
+    assert!(ArenaBst::new().inorder_keys().is_empty());
+}
+
+fn test_sync_bst() {
+    println!("\n--- Thread-safe BST Tests ---");
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<SyncBst>();
+
+    let tree = SyncBst::new();
+    for key in [15, 6, 18, 17, 20, 3, 7, 2, 4, 13, 9] {
+        tree.tree_insert(key);
+    }
+    assert!(!tree.tree_insert(15));
+    assert_eq!(tree.tree_successor(&13), Some(15));
+    assert_eq!((tree.minimum(), tree.maximum()), (Some(2), Some(20)));
+
+    // one writer keeps inserting and removing while readers check every view they get is sorted
+    let rounds = 2_000;
+    std::thread::scope(|scope| {
+        let writer = tree.clone();
+        scope.spawn(move || {
+            for key in 100..100 + rounds {
+                writer.tree_insert(key);
+                if key % 3 == 0 {
+                    writer.remove(&key);
+                }
+            }
+        });
+        for _ in 0..4 {
+            let reader = tree.clone();
+            scope.spawn(move || {
+                for _ in 0..200 {
+                    let keys = reader.inorder_keys();
+                    assert!(keys.windows(2).all(|pair| pair[0] < pair[1]));
+                    assert!(reader.contains(&15));
+                    assert!(!reader.contains(&1));
+                    assert!(!reader.is_empty());
+                }
+            });
+        }
+    });
+
+    let expected = 11 + (100..100 + rounds).filter(|key| key % 3 != 0).count();
+    println!("keys after concurrent run {}", tree.len());
+    assert_eq!(tree.len(), expected);
+    assert_eq!(tree.inorder_keys().len(), expected);
+}
+
+fn test_persistent_bst() {
+    println!("\n--- Persistent BST Tests ---");
+    let mut versions: Vec<PersistentBst> = vec![PersistentBst::new()];
+    for key in [15, 6, 18, 17, 20, 3, 7, 2, 4, 13, 9] {
+        let next = versions.last().unwrap().insert(key);
+        versions.push(next);
+    }
+    let full = versions.last().unwrap().snapshot();
+    assert!(full.ptr_eq(versions.last().unwrap()));
+    assert!(full.insert(15).ptr_eq(&full));
+    assert!(full.remove(&99).ptr_eq(&full));
+
+    // delete a leaf, a node with one child, a node with two children and the root
+    let mut current = full.snapshot();
+    for key in [4, 18, 6, 15] {
+        current = current.remove(&key);
+        versions.push(current.snapshot());
+    }
+    println!("latest version {:?}", current.inorder_keys());
+    assert_eq!(current.inorder_keys(), vec![2, 3, 7, 9, 13, 17, 20]);
+
+    // every older version is still readable as it was
+    println!("version 3 {:?}", versions[3].inorder_keys());
+    assert_eq!(versions[3].inorder_keys(), vec![6, 15, 18]);
+    assert_eq!(full.inorder_keys(), vec![2, 3, 4, 6, 7, 9, 13, 15, 17, 18, 20]);
+    assert!(full.contains(&15) && !current.contains(&15));
+    for (index, version) in versions.iter().enumerate().take(12) {
+        assert_eq!(version.len(), index);
+    }
+    assert!(versions[0].is_empty());
+}
+
+fn test_deep_clone() {
+    println!("\n--- Deep Clone Tests ---");
+    let original = build_bst(&[15, 6, 18, 17, 20, 3, 7, 2, 4, 13, 9]);
+    let copy = BstNode::deep_clone(&original);
+    assert_eq!(bst_keys(&copy), bst_keys(&original));
+    assert_eq!(BstNode::level_widths(&copy), BstNode::level_widths(&original));
+
+    // every parent link of the copy must stay inside the copy
+    let original_nodes = BstNode::inorder_nodelinks(&original);
+    for node in BstNode::inorder_nodelinks(&copy) {
+        assert!(!original_nodes.iter().any(|other| Rc::ptr_eq(other, &node)));
+        assert!(Rc::ptr_eq(&BstNode::get_root(&node), &copy));
+    }
+
+    // mutating the copy leaves the original untouched
+    let copy = BstNode::remove(Some(copy), &6).0.unwrap();
+    let copy = BstNode::remove(Some(copy), &15).0.unwrap();
+    println!("copy after removing 6 and 15 {:?}", bst_keys(&copy));
+    println!("original {:?}", bst_keys(&original));
+    assert_eq!(bst_keys(&original), vec![2, 3, 4, 6, 7, 9, 13, 15, 17, 18, 20]);
+
+    let rootlink: NodeLink = Node::new_nodelink(5);
+    rootlink.borrow_mut().add_left_child(&rootlink, 3);
+    rootlink.borrow_mut().add_right_child(&rootlink, 7);
+    let node_copy = Node::deep_clone(&rootlink);
+    node_copy.borrow().left.as_ref().unwrap().borrow_mut().value = 30;
+    let left_parent = node_copy.borrow().left.as_ref().unwrap().borrow().parent.clone();
+    assert!(Rc::ptr_eq(&left_parent.unwrap().upgrade().unwrap(), &node_copy));
+    assert_eq!(rootlink.borrow().left.as_ref().unwrap().borrow().value, 3);
+    assert_eq!(node_copy.borrow().count_nodes(), 3);
+}
+
+fn test_tree_equality() {
+    println!("\n--- Tree Equality Tests ---");
+    let keys = [15, 6, 18, 17, 20, 3, 7, 2, 4, 13, 9];
+    let tree_a = build_bst(&keys);
+    let tree_b = build_bst(&keys);
+    let mut sorted_keys = keys.to_vec();
+    sorted_keys.sort();
+    // same keys inserted in ascending order give a degenerate shape
+    let tree_c = build_bst(&sorted_keys);
+
+    println!("a and b structurally equal: {}", BstNode::structurally_equal(&tree_a, &tree_b));
+    println!("a and c structurally equal: {}", BstNode::structurally_equal(&tree_a, &tree_c));
+    assert!(BstNode::structurally_equal(&tree_a, &tree_b));
+    assert!(!BstNode::structurally_equal(&tree_a, &tree_c));
+    assert!(BstNode::same_keys(&tree_a, &tree_c));
+    assert!(!BstNode::same_keys(&tree_a, &build_bst(&[15, 6, 18])));
+
+    let mut arena_a = ArenaBst::new();
+    let mut arena_c = ArenaBst::new();
+    for (&a_key, &c_key) in keys.iter().zip(sorted_keys.iter()) {
+        arena_a.tree_insert(a_key);
+        arena_c.tree_insert(c_key);
+    }
+    assert_eq!(arena_a, arena_c);
+    arena_c.remove(&20);
+    assert!(arena_c < arena_a);
+
+    let mut versions = std::collections::HashSet::new();
+    let mut version = PersistentBst::new();
+    for key in keys {
+        version = version.insert(key);
+        versions.insert(version.clone());
+    }
+    // the same set reached through another insertion order hashes to the same entry
+    let mut other = PersistentBst::new();
+    for key in sorted_keys {
+        other = other.insert(key);
+    }
+    assert!(versions.contains(&other));
+    assert_eq!(versions.len(), keys.len());
+}
+
+fn test_interval_tree() {
+    println!("\n--- Interval Tree Tests ---");
+    let intervals = [(16, 21), (8, 9), (25, 30), (5, 8), (15, 23), (17, 19), (26, 26), (0, 3), (6, 10), (19, 20)];
+    let mut rootlink: Option<IntervalNodeLink> = None;
+    for (start, end) in intervals {
+        rootlink = Some(IntervalNode::tree_insert(rootlink, start, end));
+    }
+    let mut current_root = rootlink.unwrap();
+
+    // every max_end must be the largest end found in its subtree
+    let check_max_end = |root: &IntervalNodeLink| {
+        let mut stack = vec![Rc::clone(root)];
+        while let Some(node) = stack.pop() {
+            let node_ref = node.borrow();
+            let mut expected = node_ref.end;
+            for child in [&node_ref.left, &node_ref.right].into_iter().flatten() {
+                expected = expected.max(child.borrow().max_end);
+                stack.push(Rc::clone(child));
+            }
+            assert_eq!(node_ref.max_end, expected);
+        }
+    };
+    check_max_end(&current_root);
+
+    let found = IntervalNode::overlapping(&current_root, 22, 25);
+    println!("intervals overlapping [22, 25] {:?}", found);
+    assert_eq!(found, vec![(15, 23), (25, 30)]);
+    let found = IntervalNode::stabbing(&current_root, 8);
+    println!("intervals containing 8 {:?}", found);
+    assert_eq!(found, vec![(5, 8), (6, 10), (8, 9)]);
+    assert!(IntervalNode::overlapping(&current_root, 11, 14).is_empty());
+
+    // delete the root and a few more, the max_end of every remaining node must follow
+    for (start, end) in [(16, 21), (25, 30), (6, 10), (0, 3)] {
+        let z = IntervalNode::tree_search(&current_root, start, end).unwrap();
+        current_root = IntervalNode::tree_delete(current_root, z).unwrap();
+        check_max_end(&current_root);
+    }
+    println!("root max end after deletes {}", current_root.borrow().max_end);
+    assert_eq!(current_root.borrow().max_end, 26);
+    assert_eq!(IntervalNode::stabbing(&current_root, 8), vec![(5, 8), (8, 9)]);
+    for point in 0..32 {
+        // compare against a plain scan of the remaining intervals
+        let expected: Vec<(i32, i32)> = [(5, 8), (8, 9), (15, 23), (17, 19), (19, 20), (26, 26)]
+            .into_iter()
+            .filter(|&(start, end)| start <= point && point <= end)
+            .collect();
+        assert_eq!(IntervalNode::stabbing(&current_root, point), expected);
+    }
+}
+
+//user defined aggregate: amount of even keys in the subtree
+struct EvenCount;
+
+impl Augmentation for EvenCount {
+    type Agg = usize;
+    fn combine(left: Option<&usize>, key: i32, right: Option<&usize>) -> usize {
+        left.unwrap_or(&0) + usize::from(key % 2 == 0) + right.unwrap_or(&0)
+    }
+}
+
+fn test_augmented_bst() {
+    println!("\n--- Augmented BST Tests ---");
+    let keys = [15, 6, 18, 17, 20, 3, 7, 2, 4, 13, 9];
+    let mut sum_root: Option<AugNodeLink<Sum>> = None;
+    let mut size_root: Option<AugNodeLink<Size>> = None;
+    let mut min_root: Option<AugNodeLink<Min>> = None;
+    let mut even_root: Option<AugNodeLink<EvenCount>> = None;
+    for key in keys {
+        sum_root = Some(AugNode::tree_insert(sum_root, key));
+        size_root = Some(AugNode::tree_insert(size_root, key));
+        min_root = Some(AugNode::tree_insert(min_root, key));
+        even_root = Some(AugNode::tree_insert(even_root, key));
+    }
+    let mut sum_root = sum_root.unwrap();
+    let size_root = size_root.unwrap();
+    let min_root = min_root.unwrap();
+    let even_root = even_root.unwrap();
+
+    println!("sum of all keys {}", AugNode::aggregate(&sum_root));
+    println!("sum of keys in [4, 15] {:?}", AugNode::aggregate_range(&sum_root, 4, 15));
+    assert_eq!(AugNode::aggregate(&sum_root), 114);
+    assert_eq!(AugNode::aggregate_range(&sum_root, 4, 15), Some(54));
+    assert_eq!(AugNode::aggregate_range(&size_root, 5, 17), Some(6));
+    assert_eq!(AugNode::aggregate_range(&min_root, 5, 17), Some(6));
+    assert_eq!(AugNode::aggregate_range(&even_root, i32::MIN, 9), Some(3));
+    assert_eq!(AugNode::aggregate_range(&sum_root, 10, 12), None);
+
+    // compare every range against a plain scan, after deletes and rotations as well
+    let check_ranges = |root: &AugNodeLink<Sum>, keys: &[i32]| {
+        for a in 0..22 {
+            for b in a..22 {
+                let in_range: Vec<i64> = keys.iter().filter(|&&k| a <= k && k <= b).map(|&k| k as i64).collect();
+                let expected = if in_range.is_empty() { None } else { Some(in_range.iter().sum()) };
+                assert_eq!(AugNode::aggregate_range(root, a, b), expected);
+            }
+        }
+    };
+    check_ranges(&sum_root, &keys);
+
+    for key in [15, 6, 4] {
+        let z = AugNode::tree_search(&sum_root, &key).unwrap();
+        sum_root = AugNode::tree_delete(sum_root, z).unwrap();
+    }
+    let keys_left = [18, 17, 20, 3, 7, 2, 13, 9];
+    check_ranges(&sum_root, &keys_left);
+
+    let node_7 = AugNode::tree_search(&sum_root, &7).unwrap();
+    sum_root = AugNode::rotate_left(sum_root, &node_7);
+    let root_copy = Rc::clone(&sum_root);
+    sum_root = AugNode::rotate_right(sum_root, &root_copy);
+    sum_root = AugNode::rotate_left(sum_root, &root_copy);
+    println!("root after rotations {}", sum_root.borrow().key);
+    check_ranges(&sum_root, &keys_left);
+}
+
+fn test_bst_serde() {
+    println!("\n--- Serde Tests ---");
+    // a tree loaded from sorted keys is rebuilt balanced
+    let balanced = BstNode::from_sorted(&[2, 3, 4, 6, 7, 9, 13, 15, 17, 18, 20]).unwrap();
+    assert_eq!(bst_keys(&balanced), vec![2, 3, 4, 6, 7, 9, 13, 15, 17, 18, 20]);
+    assert_eq!(BstNode::height(&balanced), 3);
+    assert!(BstNode::from_sorted(&[]).is_none());
+
+    #[cfg(not(feature = "serde"))]
+    println!("built without the serde feature, skipping serialization");
+
+    #[cfg(feature = "serde")]
+    {
+        use crate::structure::bst_serde::{SerdeBst, ShapedBst};
+
+        let rootlink = build_bst(&[15, 6, 18, 17, 20, 3, 7, 2, 4, 13, 9]);
+        let sorted_json = serde_json::to_string(&SerdeBst(Some(Rc::clone(&rootlink)))).unwrap();
+        let shaped_json = serde_json::to_string(&ShapedBst(Some(Rc::clone(&rootlink)))).unwrap();
+        println!("sorted layout {}", sorted_json);
+        println!("shape layout {}", shaped_json);
+        assert_eq!(sorted_json, "[2,3,4,6,7,9,13,15,17,18,20]");
+
+        let loaded: SerdeBst = serde_json::from_str(&sorted_json).unwrap();
+        let loaded = loaded.0.unwrap();
+        assert!(BstNode::same_keys(&loaded, &rootlink));
+        assert!(BstNode::structurally_equal(&loaded, &balanced));
+
+        let loaded: ShapedBst = serde_json::from_str(&shaped_json).unwrap();
+        let loaded = loaded.0.unwrap();
+        assert!(BstNode::structurally_equal(&loaded, &rootlink));
+        for node in BstNode::inorder_nodelinks(&loaded) {
+            assert!(Rc::ptr_eq(&BstNode::get_root(&node), &loaded));
+        }
+
+        let empty: ShapedBst = serde_json::from_str("[null]").unwrap();
+        assert!(empty.0.is_none());
+        for bad_input in ["[3,2,1]", "[3,4,null,null,null]", "[3,2,null,null]", "[3,null,null,5]"] {
+            let error = serde_json::from_str::<ShapedBst>(bad_input)
+                .err()
+                .or_else(|| serde_json::from_str::<SerdeBst>(bad_input).err());
+            println!("{} rejected: {}", bad_input, error.unwrap());
+        }
+        assert!(serde_json::from_str::<SerdeBst>("[3,2,1]").is_err());
+    }
+}
+
+fn test_binary_format() {
+    println!("\n--- Binary Format Tests ---");
+    let rootlink = build_bst(&[15, 6, 18, 17, 20, 3, 7, 2, 4, 13, -9]);
+    let mut bytes: Vec<u8> = Vec::new();
+    BstNode::write_to(Some(&rootlink), &mut bytes).unwrap();
+    println!("bst of {} nodes written in {} bytes", BstNode::size(&rootlink), bytes.len());
+
+    let loaded = BstNode::read_from(&mut bytes.as_slice()).unwrap().unwrap();
+    assert!(BstNode::structurally_equal(&loaded, &rootlink));
+    for node in BstNode::inorder_nodelinks(&loaded) {
+        assert!(Rc::ptr_eq(&BstNode::get_root(&node), &loaded));
+    }
+
+    let mut empty: Vec<u8> = Vec::new();
+    BstNode::write_to(None, &mut empty).unwrap();
+    assert!(BstNode::read_from(&mut empty.as_slice()).unwrap().is_none());
+
+    // every truncation and every single flipped byte must be rejected
+    for len in 0..bytes.len() {
+        let result = BstNode::read_from(&mut &bytes[..len]);
+        assert!(matches!(result, Err(CodecError::Truncated)));
+    }
+    for index in 0..bytes.len() {
+        let mut corrupted = bytes.clone();
+        corrupted[index] ^= 0x10;
+        assert!(BstNode::read_from(&mut corrupted.as_slice()).is_err());
+    }
+    // a node count too large for its shape to fit in memory is a corrupt header
+    let mut huge_count: Vec<u8> = b"BTRE".to_vec();
+    huge_count.extend([1, 1]);
+    huge_count.extend([0xFF; 9]);
+    huge_count.push(0x01);
+    let result = BstNode::read_from(&mut huge_count.as_slice());
+    assert!(matches!(result, Err(CodecError::Malformed(_))));
+    let mut corrupted = bytes.clone();
+    corrupted[10] ^= 0x01;
+    println!("corrupted file: {}", BstNode::read_from(&mut corrupted.as_slice()).unwrap_err());
+
+    let node_root: NodeLink = Node::new_nodelink(5);
+    node_root.borrow_mut().add_left_child(&node_root, 3);
+    node_root.borrow_mut().add_right_child(&node_root, 7);
+    let right = node_root.borrow().right.clone().unwrap();
+    right.borrow_mut().add_right_child(&right, 10);
+    let mut node_bytes: Vec<u8> = Vec::new();
+    Node::write_to(Some(&node_root), &mut node_bytes).unwrap();
+    let loaded = Node::read_from(&mut node_bytes.as_slice()).unwrap().unwrap();
+    assert_eq!(loaded.borrow().count_nodes(), 4);
+    assert_eq!(loaded.borrow().tree_depth(), 2);
+    let loaded_right = loaded.borrow().right.clone().unwrap();
+    assert_eq!(loaded_right.borrow().right.as_ref().unwrap().borrow().value, 10);
+    println!("reading a node tree as a bst: {}", BstNode::read_from(&mut node_bytes.as_slice()).unwrap_err());
+}
+
+fn test_mapped_bst() {
+    println!("\n--- Memory-mapped BST Tests ---");
+    let path = std::env::temp_dir().join(format!("bst_eytzinger_{}.bin", std::process::id()));
+    let rootlink = build_bst(&[15, 6, 18, 17, 20, 3, 7, 2, 4, 13, 9]);
+    MappedBst::write_tree(&path, &rootlink).unwrap();
+
+    // the mapped tree reads the file itself, drop it before the file is rewritten below
+    {
+        let mapped = MappedBst::open(&path).unwrap();
+        let in_memory = MappedBst::open_in_memory(&path).unwrap();
+        println!("opened {} keys, memory-mapped: {}", mapped.len(), mapped.is_mapped());
+        assert!(!in_memory.is_mapped());
+        for tree in [&mapped, &in_memory] {
+            assert_eq!(tree.iter().collect::<Vec<i32>>(), bst_keys(&rootlink));
+            assert!(tree.search(&13) && !tree.search(&14));
+            assert_eq!((tree.floor(&14), tree.ceiling(&14)), (Some(13), Some(15)));
+            assert_eq!((tree.floor(&15), tree.ceiling(&15)), (Some(15), Some(15)));
+            assert_eq!((tree.floor(&1), tree.ceiling(&21)), (None, None));
+        }
+        println!("floor of 16 is {:?}, ceiling of 5 is {:?}", mapped.floor(&16), mapped.ceiling(&5));
+    }
+
+    // compare floor/ceiling against a plain scan for every size up to 40
+    let keys: Vec<i32> = (0..40).map(|k| k * 3).collect();
+    for len in 0..keys.len() {
+        MappedBst::write_file(&path, &keys[..len]).unwrap();
+        let tree = MappedBst::open_in_memory(&path).unwrap();
+        assert_eq!(tree.iter().collect::<Vec<i32>>(), keys[..len].to_vec());
+        for probe in -2..122 {
+            let floor = keys[..len].iter().rev().find(|&&k| k <= probe).copied();
+            let ceiling = keys[..len].iter().find(|&&k| k >= probe).copied();
+            assert_eq!((tree.floor(&probe), tree.ceiling(&probe)), (floor, ceiling));
+            assert_eq!(tree.search(&probe), keys[..len].contains(&probe));
+        }
+    }
+    assert_eq!(MappedBst::open(&path).unwrap().len(), 39);
+    assert!(MappedBst::write_file(&path, &[3, 2]).is_err());
+
+    std::fs::write(&path, b"BEYT\x01\0\0\0\x05\0\0\0\0\0\0\0").unwrap();
+    println!("truncated file: {}", MappedBst::open(&path).err().unwrap());
+    assert!(MappedBst::open(&path).is_err());
+    MappedBst::write_file(&path, &[]).unwrap();
+    assert!(MappedBst::open(&path).unwrap().is_empty());
+    std::fs::remove_file(&path).unwrap();
+}
+
+fn test_node_lookup() {
+    println!("\n--- Node Lookup Tests ---");
+    let rootlink: NodeLink = Node::new_nodelink(5);
+    rootlink.borrow_mut().add_left_child(&rootlink, 3);
+    rootlink.borrow_mut().add_right_child(&rootlink, 7);
+    let left = rootlink.borrow().left.clone().unwrap();
+    left.borrow_mut().add_left_child(&left, 2);
+    let right = rootlink.borrow().right.clone().unwrap();
+    right.borrow_mut().add_left_child(&right, 3);
+    right.borrow_mut().add_right_child(&right, 10);
+
+    // 10 only lives in the right subtree, which the lookup must reach even though a left child exists
+    let found = Node::get_node_by_value(&rootlink, 10).expect("10 should be found");
+    println!("found value {}", found.borrow().value);
+    found.borrow_mut().value = 11;
+    // the handle is the node inside the tree, so the edit shows up in the tree
+    assert!(Node::get_node_by_value(&rootlink, 10).is_none());
+    assert!(Node::get_node_by_value(&rootlink, 11).is_some());
+    assert!(Rc::ptr_eq(&Node::get_node_by_value(&rootlink, 3).unwrap(), &left));
+
+    let duplicates = Node::find_all_by_value(&rootlink, 3);
+    println!("nodes holding 3: {}", duplicates.len());
+    assert_eq!(duplicates.len(), 2);
+    assert!(Rc::ptr_eq(&duplicates[0], &left));
+    let parent = Node::upgrade_weak_to_strong(duplicates[1].borrow().parent.clone()).unwrap();
+    assert!(Rc::ptr_eq(&parent, &right));
+    assert!(Node::find_all_by_value(&rootlink, 42).is_empty());
+}
+
+fn test_node_discard() {
+    println!("\n--- Node Discard Tests ---");
+    // 5(3(2,4),7(,10))
+    let rootlink: NodeLink = Node::new_nodelink(5);
+    rootlink.borrow_mut().add_left_child(&rootlink, 3);
+    rootlink.borrow_mut().add_right_child(&rootlink, 7);
+    let left = rootlink.borrow().left.clone().unwrap();
+    left.borrow_mut().add_left_child(&left, 2);
+    left.borrow_mut().add_right_child(&left, 4);
+    let right = rootlink.borrow().right.clone().unwrap();
+    right.borrow_mut().add_right_child(&right, 10);
+
+    // 10 sits in the right subtree, behind a node that has a left child
+    let ten = Node::get_node_by_value(&rootlink, 10).unwrap();
+    let found = Node::get_node_by_full_property(&rootlink, &ten).expect("10 should be found");
+    assert!(Rc::ptr_eq(&found, &ten));
+    assert!(Node::get_node_by_full_property(&rootlink, &Node::new_nodelink(10)).is_none());
+
+    // discarding 10 must not touch the left subtree
+    let detached = Node::discard_node_by_value(&rootlink, 10).expect("10 should be discarded");
+    assert!(detached.borrow().parent.is_none());
+    assert!(right.borrow().right.is_none());
+    assert_eq!(rootlink.borrow().count_nodes(), 5);
+    assert_eq!(Node::count_nodes_by_nodelink(&left, 0), 3);
+
+    // the detached subtree keeps its own children
+    let detached = Node::discard_node_by_value(&rootlink, 3).unwrap();
+    assert!(Rc::ptr_eq(&detached, &left));
+    assert_eq!(Node::count_nodes_by_nodelink(&detached, 0), 3);
+    assert!(rootlink.borrow().left.is_none());
+    assert_eq!(rootlink.borrow().count_nodes(), 2);
+    println!("tree after discards: {} nodes", rootlink.borrow().count_nodes());
+
+    // a shallow copy shares the children, only the copy loses its link to 7
+    let shallow = rootlink.borrow().get_nodelink_copy();
+    assert!(Node::discard_node_by_value(&shallow, 7).is_some());
+    assert!(shallow.borrow().right.is_none());
+    assert!(rootlink.borrow().right.is_some());
+    assert!(right.borrow().parent.is_some());
+
+    assert!(Node::discard_node_by_value(&rootlink, 42).is_none());
+    assert!(Rc::ptr_eq(&Node::discard_node_by_value(&rootlink, 5).unwrap(), &rootlink));
+}
+
+fn test_level_order() {
+    println!("\n--- Level Order Tests ---");
+    let level_order = [Some(5), Some(3), Some(7), Some(2), Some(4), None, Some(10)];
+    let rootlink = Node::from_level_order(&level_order).unwrap().unwrap();
+    println!("level order: {:?}", Node::to_level_order(&rootlink));
+    assert_eq!(Node::to_level_order(&rootlink), level_order);
+    assert_eq!(rootlink.borrow().count_nodes(), 6);
+
+    // parent links point at the real parents
+    let ten = Node::get_node_by_value(&rootlink, 10).unwrap();
+    let seven = Node::upgrade_weak_to_strong(ten.borrow().parent.clone()).unwrap();
+    assert_eq!(seven.borrow().value, 7);
+    assert!(seven.borrow().left.is_none());
+    let root_again = Node::upgrade_weak_to_strong(seven.borrow().parent.clone()).unwrap();
+    assert!(Rc::ptr_eq(&root_again, &rootlink));
+
+    // children of a missing node are not listed, trailing Nones are optional
+    let sparse = Node::from_level_order(&[Some(1), None, Some(2), Some(3), None, None, None]).unwrap().unwrap();
+    assert_eq!(Node::to_level_order(&sparse), [Some(1), None, Some(2), Some(3)]);
+    let three = Node::get_node_by_value(&sparse, 3).unwrap();
+    assert_eq!(Node::upgrade_weak_to_strong(three.borrow().parent.clone()).unwrap().borrow().value, 2);
+
+    assert!(Node::from_level_order(&[]).unwrap().is_none());
+    assert!(Node::from_level_order(&[None]).unwrap().is_none());
+
+    // values that no node can take are rejected, not dropped
+    let error = Node::from_level_order(&[Some(1), None, None, Some(5)]).unwrap_err();
+    println!("rejected: {}", error);
+    assert_eq!(error, LevelOrderError { index: 3, value: 5 });
+    assert_eq!(
+        Node::from_level_order(&[None, Some(1)]).unwrap_err(),
+        LevelOrderError { index: 1, value: 1 }
+    );
+    // both slots of 2 are empty, the None after them is only padding
+    assert!(Node::from_level_order(&[Some(1), Some(2), None, None, None, None]).is_ok());
+    assert_eq!(Node::to_level_order(&Node::new_nodelink(8)), [Some(8)]);
+}
+
+fn test_tree_rebuild() {
+    println!("\n--- Tree Rebuild Tests ---");
+    // 5(3(2,4),7(,10))
+    let expected = [Some(5), Some(3), Some(7), Some(2), Some(4), None, Some(10)];
+    let preorder = [5, 3, 2, 4, 7, 10];
+    let inorder = [2, 3, 4, 5, 7, 10];
+    let postorder = [2, 4, 3, 10, 7, 5];
+
+    let from_pre = Node::from_preorder_inorder(&preorder, &inorder).unwrap().unwrap();
+    println!("from pre-order and in-order: {:?}", Node::to_level_order(&from_pre));
+    assert_eq!(Node::to_level_order(&from_pre), expected);
+    let from_post = Node::from_postorder_inorder(&postorder, &inorder).unwrap().unwrap();
+    assert_eq!(Node::to_level_order(&from_post), expected);
+    let from_bst = Node::from_bst_preorder(&preorder).unwrap().unwrap();
+    assert_eq!(Node::to_level_order(&from_bst), expected);
+
+    let ten = Node::get_node_by_value(&from_post, 10).unwrap();
+    let seven = Node::upgrade_weak_to_strong(ten.borrow().parent.clone()).unwrap();
+    assert_eq!(seven.borrow().value, 7);
+
+    // values don't need to be ordered for the pairs, only for the BST constructor
+    let unordered = Node::from_preorder_inorder(&[1, 9, 8], &[9, 1, 8]).unwrap().unwrap();
+    assert_eq!(Node::to_level_order(&unordered), [Some(1), Some(9), Some(8)]);
+    assert!(Node::from_preorder_inorder(&[], &[]).unwrap().is_none());
+
+    let errors = [
+        Node::from_preorder_inorder(&[5, 3], &[3]).unwrap_err(),
+        Node::from_preorder_inorder(&[1, 1], &[1, 1]).unwrap_err(),
+        Node::from_postorder_inorder(&[2, 4, 3], &[2, 3, 6]).unwrap_err(),
+        // only 3 is left of 1 in-order, so 2 can't come right after 1 in pre-order
+        Node::from_preorder_inorder(&[1, 2, 3], &[3, 1, 2]).unwrap_err(),
+        Node::from_bst_preorder(&[5, 3, 7, 4]).unwrap_err(),
+    ];
+    for error in &errors {
+        println!("rejected: {}", error);
+    }
+    assert_eq!(errors[0], TraversalError::LengthMismatch { order: 2, inorder: 1 });
+    assert_eq!(errors[1], TraversalError::DuplicateValue(1));
+    assert_eq!(errors[2], TraversalError::Inconsistent(4));
+    assert_eq!(errors[3], TraversalError::Inconsistent(2));
+    assert_eq!(errors[4], TraversalError::NotBstPreorder { index: 3, value: 4 });
+}
+
+fn test_tree_notation() {
+    println!("\n--- Tree Notation Tests ---");
+    let text = "5(3(2,4),7(,10))";
+    let rootlink = Node::from_notation(text).unwrap().unwrap();
+    println!("parsed {} into level order {:?}", text, Node::to_level_order(&rootlink));
+    assert_eq!(
+        Node::to_level_order(&rootlink),
+        [Some(5), Some(3), Some(7), Some(2), Some(4), None, Some(10)]
+    );
+    assert_eq!(Node::to_notation(Some(&rootlink)), text);
+    let ten = Node::get_node_by_value(&rootlink, 10).unwrap();
+    assert_eq!(Node::upgrade_weak_to_strong(ten.borrow().parent.clone()).unwrap().borrow().value, 7);
+
+    // the prime.dot example tree, written back as a dot file it must match the original
+    let prime = Node::from_notation("5(3,7)").unwrap().unwrap();
+    let dot_path = std::env::temp_dir().join(format!("prime_notation_{}.dot", std::process::id()));
+    generate_dotfile(&prime, dot_path.to_str().unwrap());
+    let dot = std::fs::read_to_string(&dot_path).unwrap();
+    std::fs::remove_file(&dot_path).unwrap();
+    assert_eq!(dot, include_str!("../prime.dot"));
+    assert_eq!(Node::to_notation(Some(&prime)), "5(3,7)");
+
+    // the same tree as a BST, whitespace and the short form for a lone left child are accepted
+    let bst = BstNode::from_notation(" 5 ( 3 ( 2 ) , 7 ( , 10 ) ) ").unwrap().unwrap();
+    assert_eq!(BstNode::to_notation(Some(&bst)), "5(3(2,),7(,10))");
+    assert_eq!(bst_keys(&bst), vec![2, 3, 5, 7, 10]);
+    let round_trip = BstNode::from_notation(&BstNode::to_notation(Some(&bst))).unwrap().unwrap();
+    assert!(BstNode::structurally_equal(&bst, &round_trip));
+    assert!(Node::from_notation("").unwrap().is_none());
+    assert_eq!(BstNode::to_notation(None), "");
+
+    let malformed = [
+        ("5(3,7", 5),
+        ("5(3,7))", 6),
+        ("5(3,7,9)", 5),
+        ("5(3)(7)", 4),
+        ("5(x,7)", 2),
+        ("5,3", 1),
+        ("5(3,99999999999)", 4),
+    ];
+    for (input, position) in malformed {
+        let error: NotationError = Node::from_notation(input).unwrap_err();
+        println!("{:?} rejected: {}", input, error);
+        assert_eq!(error.position, position, "wrong position for {:?}", input);
+    }
+    let error = BstNode::from_notation("5(3(2,6),7)").unwrap_err();
+    println!("not a BST: {}", error);
+    assert_eq!(error.position, 6);
+}
+
+fn test_tree_bst_conversion() {
+    println!("\n--- Tree/BST Conversion Tests ---");
+    let bst_shaped = Node::from_notation("5(3(2,4),7(,10))").unwrap().unwrap();
+    assert!(bst_shaped.borrow().is_bst());
+    // every parent/child pair is ordered, but 6 sits left of 5 two levels up
+    let locally_ordered = Node::from_notation("5(3(2,6),7)").unwrap().unwrap();
+    println!("5(3(2,6),7) is a BST: {}", locally_ordered.borrow().is_bst());
+    assert!(!locally_ordered.borrow().is_bst());
+    assert!(!Node::from_notation("5(5,7)").unwrap().unwrap().borrow().is_bst());
+    assert!(Node::new_nodelink(1).borrow().is_bst());
+
+    // any tree becomes a balanced BST with the same values, duplicates kept once
+    let unordered = Node::from_notation("4(9(1,4),2(,7))").unwrap().unwrap();
+    let bst = unordered.borrow().to_bst();
+    println!("to_bst: {}", BstNode::to_notation(Some(&bst)));
+    assert_eq!(bst_keys(&bst), vec![1, 2, 4, 7, 9]);
+    assert_eq!(BstNode::height(&bst), 2);
+    assert_eq!(Node::to_notation(Some(&unordered)), "4(9(1,4),2(,7))");
+
+    // and back, keeping the exact shape and the parent links
+    let original = BstNode::from_notation("5(3(2,),7(,10))").unwrap().unwrap();
+    let converted = Node::from_bst(&original);
+    assert_eq!(Node::to_notation(Some(&converted)), "5(3(2,),7(,10))");
+    assert!(converted.borrow().is_bst());
+    let ten = Node::get_node_by_value(&converted, 10).unwrap();
+    assert_eq!(Node::upgrade_weak_to_strong(ten.borrow().parent.clone()).unwrap().borrow().value, 7);
+    let back = converted.borrow().to_bst();
+    assert_eq!(bst_keys(&back), bst_keys(&original));
+}
+
+fn test_tree_shape() {
+    println!("\n--- Tree Shape Tests ---");
+    let tree = |text: &str| Node::from_notation(text).unwrap().unwrap();
+    let prime = tree("5(3(2,4),7(,10))");
+    let perfect = tree("4(2(1,3),6(5,7))");
+    let full = tree("1(2,3(4,5))");
+    let chain = tree("1(,2(,3(,4)))");
+    let leaf = tree("8");
+
+    // mirror swaps every pair of children, parents stay the same
+    prime.borrow_mut().mirror();
+    println!("mirrored: {}", Node::to_notation(Some(&prime)));
+    assert_eq!(Node::to_notation(Some(&prime)), "5(7(10,),3(4,2))");
+    let ten = Node::get_node_by_value(&prime, 10).unwrap();
+    assert_eq!(Node::upgrade_weak_to_strong(ten.borrow().parent.clone()).unwrap().borrow().value, 7);
+    prime.borrow_mut().mirror();
+    assert_eq!(Node::to_notation(Some(&prime)), "5(3(2,4),7(,10))");
+
+    // diameter in edges, 2 -> 3 -> 5 -> 7 -> 10
+    assert_eq!(prime.borrow().diameter(), 4);
+    assert_eq!(chain.borrow().diameter(), 3);
+    assert_eq!(leaf.borrow().diameter(), 0);
+    assert_eq!(tree("1(2(3(4,),5(,6)),)").borrow().diameter(), 4);
+
+    assert!(prime.borrow().is_balanced());
+    assert!(!chain.borrow().is_balanced());
+    // both root subtrees have height 2, but 2 itself is unbalanced
+    assert!(!tree("1(2(3(4,),),5(6,7))").borrow().is_balanced());
+
+    assert!(perfect.borrow().is_complete());
+    assert!(tree("1(2(4,5),3(6,))").borrow().is_complete());
+    assert!(!prime.borrow().is_complete());
+    assert!(!tree("1(2(,5),3)").borrow().is_complete());
+
+    assert!(full.borrow().is_full());
+    assert!(perfect.borrow().is_full());
+    assert!(!prime.borrow().is_full());
+    assert!(leaf.borrow().is_full());
+
+    assert!(perfect.borrow().is_perfect());
+    assert!(leaf.borrow().is_perfect());
+    assert!(!full.borrow().is_perfect());
+    assert!(!tree("1(2(4,5),3(6,))").borrow().is_perfect());
+
+    assert_eq!(prime.borrow().max_width(), 3);
+    assert_eq!(perfect.borrow().max_width(), 4);
+    assert_eq!(chain.borrow().max_width(), 1);
+
+    let leaves: Vec<i32> = Node::leaves(&prime).iter().map(|node| node.borrow().value).collect();
+    println!("leaves: {:?}", leaves);
+    assert_eq!(leaves, vec![2, 4, 10]);
+    assert!(Rc::ptr_eq(&Node::leaves(&prime)[2], &ten));
+    assert!(Rc::ptr_eq(&Node::leaves(&leaf)[0], &leaf));
+
+    // a degenerate tree deep enough to overflow a recursive walk
+    let depth = 100_000;
+    let deep = Node::new_nodelink(0);
+    let mut tail = Rc::clone(&deep);
+    for value in 1..depth {
+        tail.borrow_mut().add_right_child(&tail, value);
+        let next = tail.borrow().right.clone().unwrap();
+        tail = next;
+    }
+    assert_eq!(deep.borrow().diameter(), depth as usize - 1);
+    assert!(!deep.borrow().is_balanced());
+    assert!(!deep.borrow().is_complete());
+    assert!(!deep.borrow().is_full());
+    assert_eq!(deep.borrow().max_width(), 1);
+    deep.borrow_mut().mirror();
+    assert!(deep.borrow().right.is_none());
+    assert_eq!(Node::leaves(&deep).len(), 1);
+}
+
+fn test_tree_compare() {
+    println!("\n--- Tree Compare Tests ---");
+    let tree = |text: &str| Node::from_notation(text).unwrap().unwrap();
+    let prime = tree("5(3(2,4),7(,10))");
+
+    assert!(prime.borrow().contains_subtree(&tree("3(2,4)").borrow()));
+    assert!(prime.borrow().contains_subtree(&tree("7(,10)").borrow()));
+    assert!(prime.borrow().contains_subtree(&tree("10").borrow()));
+    assert!(prime.borrow().contains_subtree(&prime.borrow()));
+    // same values in another shape, or only the top part of a subtree
+    assert!(!prime.borrow().contains_subtree(&tree("7(10,)").borrow()));
+    assert!(!prime.borrow().contains_subtree(&tree("3(2,)").borrow()));
+    assert!(!prime.borrow().contains_subtree(&tree("3").borrow()));
+    // the values of both runs must line up, not just the digits
+    assert!(!tree("1(12,)").borrow().contains_subtree(&tree("2").borrow()));
+    println!(
+        "5(3(2,4),7(,10)) contains 3(2,4): {}",
+        prime.borrow().contains_subtree(&tree("3(2,4)").borrow())
+    );
+
+    assert!(prime.borrow().is_isomorphic(&tree("5(7(10,),3(4,2))").borrow()));
+    assert!(prime.borrow().is_isomorphic(&tree("5(3(4,2),7(10,))").borrow()));
+    assert!(!prime.borrow().is_isomorphic(&tree("5(3(2,4),7(,11))").borrow()));
+    // same values, but 4 and 10 traded places across subtrees
+    assert!(!prime.borrow().is_isomorphic(&tree("5(3(2,10),7(,4))").borrow()));
+
+    // full property matching now compares whole child subtrees, not just the child values
+    let copy = Node::deep_clone(&prime);
+    let copied_three = Node::get_node_by_value(&copy, 3).unwrap();
+    let found = Node::get_node_by_full_property(&prime, &copied_three).unwrap();
+    assert!(Rc::ptr_eq(&found, &Node::get_node_by_value(&prime, 3).unwrap()));
+    let copied_two = Node::get_node_by_value(&copy, 2).unwrap();
+    copied_two.borrow_mut().add_left_child(&copied_two, 1);
+    assert!(Node::get_node_by_full_property(&prime, &copied_three).is_none());
+}
+
+fn test_tree_paths() {
+    println!("\n--- Tree Path Tests ---");
+    let values = |path: &Vec<NodeLink>| path.iter().map(|node| node.borrow().value).collect::<Vec<i32>>();
+    let prime = Node::from_notation("5(3(2,4),7(,10))").unwrap().unwrap();
+
+    let paths: Vec<Vec<i32>> = Node::root_to_leaf_paths(&prime).iter().map(values).collect();
+    println!("root to leaf paths: {:?}", paths);
+    assert_eq!(paths, vec![vec![5, 3, 2], vec![5, 3, 4], vec![5, 7, 10]]);
+    // the paths hold the nodes of the tree, not copies
+    let ten = Node::get_node_by_value(&prime, 10).unwrap();
+    assert!(Rc::ptr_eq(&Node::root_to_leaf_paths(&prime)[2][2], &ten));
+    // a subtree is walked from its own root
+    let seven = Node::get_node_by_value(&prime, 7).unwrap();
+    let sub_paths: Vec<Vec<i32>> = Node::root_to_leaf_paths(&seven).iter().map(values).collect();
+    assert_eq!(sub_paths, vec![vec![7, 10]]);
+    // a shallow copy shares its children, whose parent links lead to the original and not to the copy
+    let seven_copy = seven.borrow().get_nodelink_copy();
+    let copy_paths: Vec<Vec<i32>> = Node::root_to_leaf_paths(&seven_copy).iter().map(values).collect();
+    assert_eq!(copy_paths, vec![vec![7, 10]]);
+    assert!(Rc::ptr_eq(&Node::root_to_leaf_paths(&seven_copy)[0][0], &seven_copy));
+
+    let with_sum: Vec<Vec<i32>> = Node::paths_with_sum(&prime, 12).iter().map(values).collect();
+    assert_eq!(with_sum, vec![vec![5, 3, 4]]);
+    let decision = Node::from_notation("1(2(3,-1(,4)),4(-2,3))").unwrap().unwrap();
+    let with_sum: Vec<Vec<i32>> = Node::paths_with_sum(&decision, 6).iter().map(values).collect();
+    assert_eq!(with_sum, vec![vec![1, 2, 3], vec![1, 2, -1, 4]]);
+    assert!(Node::paths_with_sum(&prime, 100).is_empty());
+
+    let (sum, path) = Node::max_path_sum(&prime);
+    println!("max path sum {} along {:?}", sum, values(&path));
+    assert_eq!((sum, values(&path)), (29, vec![4, 3, 5, 7, 10]));
+    assert!(Rc::ptr_eq(path.last().unwrap(), &ten));
+    // negative values are left out when they don't help
+    let negative = Node::from_notation("-10(9,20(15,7))").unwrap().unwrap();
+    let (sum, path) = Node::max_path_sum(&negative);
+    assert_eq!((sum, values(&path)), (42, vec![15, 20, 7]));
+    let all_negative = Node::from_notation("-3(-1,-2)").unwrap().unwrap();
+    let (sum, path) = Node::max_path_sum(&all_negative);
+    assert_eq!((sum, values(&path)), (-1, vec![-1]));
+}
+
+fn test_tree_views() {
+    println!("\n--- Tree View Tests ---");
+    let values = |nodes: Vec<PositionedNode>| nodes.iter().map(|p| p.node.borrow().value).collect::<Vec<i32>>();
+    let prime = Node::from_notation("5(3(2,4),7(,10))").unwrap().unwrap();
+
+    let vertical: Vec<PositionedNode> = Node::vertical_order(&prime).collect();
+    let columns: Vec<(i32, usize, i32)> = vertical
+        .iter()
+        .map(|p| (p.column, p.level, p.node.borrow().value))
+        .collect();
+    println!("vertical order (column, level, value): {:?}", columns);
+    assert_eq!(
+        columns,
+        vec![(-2, 2, 2), (-1, 1, 3), (0, 0, 5), (0, 2, 4), (1, 1, 7), (2, 2, 10)]
+    );
+    // the yielded nodes are the nodes of the tree
+    let ten = Node::get_node_by_value(&prime, 10).unwrap();
+    assert!(Rc::ptr_eq(&vertical[5].node, &ten));
+
+    assert_eq!(values(Node::boundary(&prime).collect()), vec![5, 3, 2, 4, 10, 7]);
+    assert_eq!(values(Node::zigzag_level_order(&prime).collect()), vec![5, 7, 3, 2, 4, 10]);
+    assert_eq!(values(Node::left_side_view(&prime).collect()), vec![5, 3, 2]);
+    assert_eq!(values(Node::right_side_view(&prime).collect()), vec![5, 7, 10]);
+    let levels: Vec<usize> = Node::right_side_view(&prime).map(|p| p.level).collect();
+    assert_eq!(levels, vec![0, 1, 2]);
+
+    // the left edge falls back to right children, the right edge to left children
+    let edges = Node::from_notation("1(2(,3(4,5)),6(7(8,),))").unwrap().unwrap();
+    assert_eq!(values(Node::boundary(&edges).collect()), vec![1, 2, 3, 4, 5, 8, 7, 6]);
+    // a lone root is a leaf, shown once
+    let single = Node::new_nodelink(9);
+    assert_eq!(values(Node::boundary(&single).collect()), vec![9]);
+    assert_eq!(values(Node::left_side_view(&single).collect()), vec![9]);
+
+    // level based traversals are lazy, taking the top of a tree does not walk the rest
+    let first_two: Vec<i32> = Node::zigzag_level_order(&prime).take(2).map(|p| p.node.borrow().value).collect();
+    assert_eq!(first_two, vec![5, 7]);
+}
+
 #[allow(dead_code)]
 fn test_binary_tree() {
     //create the nodelink of the root node
@@ -233,23 +1382,21 @@ fn test_binary_tree() {
     let subtree_count = Node::count_nodes_by_nodelink(&right_subtree.clone().unwrap(), 0);
     println!("Amount of nodes in current subtree: {0}", subtree_count);
 
-    let _left_subtree_sibling = Node::get_sibling(&left_subtree.as_ref().unwrap());
+    let _left_subtree_sibling = Node::get_sibling(left_subtree.as_ref().unwrap());
 
-    let left_subtree = rootlink.borrow().get_node_by_value(3);
+    let left_subtree = Node::get_node_by_value(&rootlink, 3);
     println!("left subtree seek by value {:?}", left_subtree);
 
-    let another_left_subtree = rootlink
-        .borrow()
-        .get_node_by_full_property(&left_subtree.as_ref().unwrap());
+    let another_left_subtree = Node::get_node_by_full_property(&rootlink, left_subtree.as_ref().unwrap());
     println!(
         "left subtree seek by full property {:?}",
         another_left_subtree
     );
 
-    let rootlink2 = rootlink.borrow().get_nodelink_copy();
+    let rootlink2 = Node::deep_clone(&rootlink);
 
-    let flag = rootlink2.borrow_mut().discard_node_by_value(3);
-    println!("status of node deletion: {0}", flag);
+    let discarded = Node::discard_node_by_value(&rootlink2, 3);
+    println!("status of node deletion: {0}", discarded.is_some());
 
     //print the tree again
     main_tree_path = "prime_t3.dot";
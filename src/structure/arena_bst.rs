@@ -0,0 +1,284 @@
+//this package implement the BST on top of an arena, nodes live in one Vec and link each other by index
+//no Rc/RefCell: no refcount traffic, no runtime borrow panics and nodes stay close in memory
+
+use std::cmp::Ordering;
+use std::hash::{Hash, Hasher};
+
+pub type NodeIndex = u32;
+
+#[derive(Debug, Clone)]
+struct ArenaNode {
+    key: i32,
+    parent: Option<NodeIndex>,
+    left: Option<NodeIndex>,
+    right: Option<NodeIndex>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ArenaBst {
+    //None marks a vacant slot, waiting in the free list
+    nodes: Vec<Option<ArenaNode>>,
+    free: Vec<NodeIndex>,
+    root: Option<NodeIndex>,
+    len: usize,
+}
+
+impl ArenaBst {
+    pub fn new() -> Self {
+        ArenaBst::default()
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        ArenaBst {
+            nodes: Vec::with_capacity(capacity),
+            ..ArenaBst::default()
+        }
+    }
+
+    pub fn root(&self) -> Option<NodeIndex> {
+        self.root
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    //private interface, panics on a vacant slot since that means the handle is stale
+    fn node(&self, index: NodeIndex) -> &ArenaNode {
+        self.nodes[index as usize]
+            .as_ref()
+            .expect("node index points to a deleted node")
+    }
+
+    fn node_mut(&mut self, index: NodeIndex) -> &mut ArenaNode {
+        self.nodes[index as usize]
+            .as_mut()
+            .expect("node index points to a deleted node")
+    }
+
+    /**
+     * Key stored at `index`, None if the slot is vacant
+     * Note that a vacant slot is reused by the next insert, so handles of deleted nodes must be dropped
+     */
+    pub fn key(&self, index: NodeIndex) -> Option<i32> {
+        self.nodes
+            .get(index as usize)
+            .and_then(|slot| slot.as_ref())
+            .map(|node| node.key)
+    }
+
+    pub fn parent(&self, index: NodeIndex) -> Option<NodeIndex> {
+        self.node(index).parent
+    }
+
+    pub fn left(&self, index: NodeIndex) -> Option<NodeIndex> {
+        self.node(index).left
+    }
+
+    pub fn right(&self, index: NodeIndex) -> Option<NodeIndex> {
+        self.node(index).right
+    }
+
+    //take a slot from the free list before growing the arena
+    fn allocate(&mut self, node: ArenaNode) -> NodeIndex {
+        if let Some(index) = self.free.pop() {
+            self.nodes[index as usize] = Some(node);
+            index
+        } else {
+            let index = NodeIndex::try_from(self.nodes.len()).expect("arena is full");
+            self.nodes.push(Some(node));
+            index
+        }
+    }
+
+    //search the tree which node fit the key (Iterative version)
+    pub fn tree_search(&self, key: &i32) -> Option<NodeIndex> {
+        let mut current = self.root;
+        while let Some(index) = current {
+            let node = self.node(index);
+            if *key == node.key {
+                return Some(index);
+            }
+            current = if *key < node.key { node.left } else { node.right };
+        }
+        None
+    }
+
+    pub fn minimum(&self, mut index: NodeIndex) -> NodeIndex {
+        while let Some(left) = self.node(index).left {
+            index = left;
+        }
+        index
+    }
+
+    pub fn maximum(&self, mut index: NodeIndex) -> NodeIndex {
+        while let Some(right) = self.node(index).right {
+            index = right;
+        }
+        index
+    }
+
+    /**
+     * Find node successor according to the book
+     * Should return None, if index holds the highest key in the tree
+     */
+    pub fn tree_successor(&self, index: NodeIndex) -> Option<NodeIndex> {
+        if let Some(right) = self.node(index).right {
+            return Some(self.minimum(right));
+        }
+        let mut current = index;
+        let mut parent = self.node(current).parent;
+        while let Some(p_index) = parent {
+            if self.node(p_index).left == Some(current) {
+                return Some(p_index);
+            }
+            current = p_index;
+            parent = self.node(current).parent;
+        }
+        None
+    }
+
+    /**
+     * Insert a new node with the given key
+     * Returns the index of the new node, None if the key already exists
+     */
+    pub fn tree_insert(&mut self, key: i32) -> Option<NodeIndex> {
+        let mut y: Option<NodeIndex> = None; // trailing pointer
+        let mut x = self.root;
+        while let Some(index) = x {
+            let node = self.node(index);
+            if key == node.key {
+                return None;
+            }
+            y = Some(index);
+            x = if key < node.key { node.left } else { node.right };
+        }
+
+        let z = self.allocate(ArenaNode {
+            key,
+            parent: y,
+            left: None,
+            right: None,
+        });
+        match y {
+            None => self.root = Some(z),
+            Some(y_index) => {
+                if key < self.node(y_index).key {
+                    self.node_mut(y_index).left = Some(z);
+                } else {
+                    self.node_mut(y_index).right = Some(z);
+                }
+            }
+        }
+        self.len += 1;
+        Some(z)
+    }
+
+    /**
+     * Replace the subtree rooted at `u` with the subtree rooted at `v`
+     */
+    pub fn transplant(&mut self, u: NodeIndex, v: Option<NodeIndex>) {
+        let u_parent = self.node(u).parent;
+        match u_parent {
+            None => self.root = v,
+            Some(p_index) => {
+                if self.node(p_index).left == Some(u) {
+                    self.node_mut(p_index).left = v;
+                } else {
+                    self.node_mut(p_index).right = v;
+                }
+            }
+        }
+        if let Some(v_index) = v {
+            self.node_mut(v_index).parent = u_parent;
+        }
+    }
+
+    /**
+     * Deletes the node at `z`, its slot goes to the free list
+     * Returns the removed key, None if the slot was already vacant
+     */
+    pub fn tree_delete(&mut self, z: NodeIndex) -> Option<i32> {
+        self.key(z)?;
+        let (z_left, z_right) = (self.node(z).left, self.node(z).right);
+
+        match (z_left, z_right) {
+            (None, _) => self.transplant(z, z_right),
+            (_, None) => self.transplant(z, z_left),
+            (Some(z_l), Some(z_r)) => {
+                let y = self.minimum(z_r); // y is the successor
+                if self.node(y).parent != Some(z) {
+                    let y_right = self.node(y).right;
+                    self.transplant(y, y_right);
+                    self.node_mut(y).right = Some(z_r);
+                    self.node_mut(z_r).parent = Some(y);
+                }
+                self.transplant(z, Some(y));
+                self.node_mut(y).left = Some(z_l);
+                self.node_mut(z_l).parent = Some(y);
+            }
+        }
+
+        let removed = self.nodes[z as usize].take().map(|node| node.key);
+        self.free.push(z);
+        self.len -= 1;
+        removed
+    }
+
+    /**
+     * Search the node holding `key` and delete it, returning the removed key
+     */
+    pub fn remove(&mut self, key: &i32) -> Option<i32> {
+        let z = self.tree_search(key)?;
+        self.tree_delete(z)
+    }
+
+    /**
+     * All keys in ascending order, walking the successor chain from the minimum
+     */
+    pub fn inorder_keys(&self) -> Vec<i32> {
+        let mut keys: Vec<i32> = Vec::with_capacity(self.len);
+        let mut current = self.root.map(|root| self.minimum(root));
+        while let Some(index) = current {
+            keys.push(self.node(index).key);
+            current = self.tree_successor(index);
+        }
+        keys
+    }
+}
+
+/**
+ * Two arena trees are equal when they hold the same keys, whatever their shape or slot layout
+ * Only arena trees compare this way, SyncBst doesn't: another handle can change a shared tree
+ * while it sits in a HashSet or gets compared, take a snapshot with inorder_keys and compare those
+ */
+impl PartialEq for ArenaBst {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.inorder_keys() == other.inorder_keys()
+    }
+}
+
+impl Eq for ArenaBst {}
+
+impl Hash for ArenaBst {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.inorder_keys().hash(state);
+    }
+}
+
+//lexicographic order of the sorted keys
+impl PartialOrd for ArenaBst {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ArenaBst {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.inorder_keys().cmp(&other.inorder_keys())
+    }
+}
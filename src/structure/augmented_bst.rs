@@ -0,0 +1,349 @@
+use std::cell::RefCell;
+use std::rc::{Rc, Weak};
+pub type AugNodeLink<A> = Rc<RefCell<AugNode<A>>>;
+pub type WeakAugNodeLink<A> = Weak<RefCell<AugNode<A>>>;
+
+//this package implement a BST where every node caches an aggregate of its whole subtree
+//the aggregate is user defined through the Augmentation trait, e.g. subtree size, key sum or key min
+
+/**
+ * `combine` builds the aggregate of a subtree from the aggregates of its children and the key of its root.
+ * A missing child is passed as None.
+ * For aggregate_range the combination must be associative on the in-order key sequence (a monoid).
+ */
+pub trait Augmentation {
+    type Agg: Clone;
+    fn combine(left: Option<&Self::Agg>, key: i32, right: Option<&Self::Agg>) -> Self::Agg;
+}
+
+//amount of keys in the subtree
+pub struct Size;
+
+impl Augmentation for Size {
+    type Agg = usize;
+    fn combine(left: Option<&usize>, _key: i32, right: Option<&usize>) -> usize {
+        left.unwrap_or(&0) + 1 + right.unwrap_or(&0)
+    }
+}
+
+//sum of the keys in the subtree, kept in i64 so it doesn't overflow on i32 keys
+pub struct Sum;
+
+impl Augmentation for Sum {
+    type Agg = i64;
+    fn combine(left: Option<&i64>, key: i32, right: Option<&i64>) -> i64 {
+        left.unwrap_or(&0) + key as i64 + right.unwrap_or(&0)
+    }
+}
+
+//smallest key in the subtree
+pub struct Min;
+
+impl Augmentation for Min {
+    type Agg = i32;
+    fn combine(left: Option<&i32>, key: i32, _right: Option<&i32>) -> i32 {
+        //in a BST the left subtree always holds the smaller keys
+        left.map_or(key, |l| (*l).min(key))
+    }
+}
+
+pub struct AugNode<A: Augmentation> {
+    pub key: i32,
+    pub agg: A::Agg,
+    pub parent: Option<WeakAugNodeLink<A>>,
+    pub left: Option<AugNodeLink<A>>,
+    pub right: Option<AugNodeLink<A>>,
+}
+
+impl<A: Augmentation> AugNode<A> {
+    pub fn new_aug_nodelink(key: i32) -> AugNodeLink<A> {
+        Rc::new(RefCell::new(AugNode {
+            key,
+            agg: A::combine(None, key, None),
+            parent: None,
+            left: None,
+            right: None,
+        }))
+    }
+
+    fn downgrade(node: &AugNodeLink<A>) -> WeakAugNodeLink<A> {
+        Rc::downgrade(node)
+    }
+
+    fn upgrade_weak_to_strong(node: Option<WeakAugNodeLink<A>>) -> Option<AugNodeLink<A>> {
+        node.and_then(|weak_node| weak_node.upgrade())
+    }
+
+    fn parent_of(node: &AugNodeLink<A>) -> Option<AugNodeLink<A>> {
+        AugNode::upgrade_weak_to_strong(node.borrow().parent.clone())
+    }
+
+    //recompute the aggregate of `node` from its children
+    fn update_agg(node: &AugNodeLink<A>) {
+        let mut node_mut = node.borrow_mut();
+        let left = node_mut.left.as_ref().map(|l| l.borrow().agg.clone());
+        let right = node_mut.right.as_ref().map(|r| r.borrow().agg.clone());
+        node_mut.agg = A::combine(left.as_ref(), node_mut.key, right.as_ref());
+    }
+
+    //recompute the aggregates from `node` up to the root
+    fn update_agg_upward(node: Option<AugNodeLink<A>>) {
+        let mut current = node;
+        while let Some(c_node) = current {
+            AugNode::update_agg(&c_node);
+            current = AugNode::parent_of(&c_node);
+        }
+    }
+
+    /**
+     * Aggregate of the whole tree rooted at `root`
+     */
+    pub fn aggregate(root: &AugNodeLink<A>) -> A::Agg {
+        root.borrow().agg.clone()
+    }
+
+    //search the current tree which node fit the key (Iterative version)
+    pub fn tree_search(root: &AugNodeLink<A>, key: &i32) -> Option<AugNodeLink<A>> {
+        let mut current = Some(Rc::clone(root));
+        while let Some(node) = current {
+            let node_key = node.borrow().key;
+            if *key == node_key {
+                return Some(node);
+            }
+            current = if *key < node_key {
+                node.borrow().left.clone()
+            } else {
+                node.borrow().right.clone()
+            };
+        }
+        None
+    }
+
+    pub fn minimum_nodelink(mut node: AugNodeLink<A>) -> AugNodeLink<A> {
+        loop {
+            let left_child = node.borrow().left.clone();
+            match left_child {
+                Some(left_node) => node = left_node,
+                None => return node,
+            }
+        }
+    }
+
+    /**
+     * Insert a new node with the given key into the tree rooted at `root`.
+     * Returns the updated root of the tree, duplicate keys are not inserted.
+     */
+    pub fn tree_insert(root: Option<AugNodeLink<A>>, key: i32) -> AugNodeLink<A> {
+        let mut y: Option<AugNodeLink<A>> = None; // trailing pointer
+        let mut x = root.clone();
+        while let Some(current_x) = x {
+            let x_key = current_x.borrow().key;
+            if x_key == key {
+                println!("Key {} already exists, not inserting.", key);
+                return root.unwrap();
+            }
+            x = if key < x_key {
+                current_x.borrow().left.clone()
+            } else {
+                current_x.borrow().right.clone()
+            };
+            y = Some(current_x);
+        }
+
+        let z_node: AugNodeLink<A> = AugNode::new_aug_nodelink(key);
+        match y {
+            None => z_node,
+            Some(y_node) => {
+                z_node.borrow_mut().parent = Some(AugNode::downgrade(&y_node));
+                if key < y_node.borrow().key {
+                    y_node.borrow_mut().left = Some(z_node);
+                } else {
+                    y_node.borrow_mut().right = Some(z_node);
+                }
+                AugNode::update_agg_upward(Some(y_node));
+                root.unwrap()
+            }
+        }
+    }
+
+    /**
+     * Replace the subtree rooted at `u` with the subtree rooted at `v`, then fix the aggregates above it.
+     * Returns the root of the tree, None if `u` was the root and `v` is None.
+     */
+    pub fn transplant(
+        root: AugNodeLink<A>,
+        u: &AugNodeLink<A>,
+        v: Option<AugNodeLink<A>>,
+    ) -> Option<AugNodeLink<A>> {
+        let u_parent = AugNode::parent_of(u);
+        if let Some(v_node) = &v {
+            v_node.borrow_mut().parent = u_parent.as_ref().map(AugNode::downgrade);
+        }
+
+        match u_parent {
+            None => v,
+            Some(u_p) => {
+                let is_left = u_p.borrow().left.as_ref().is_some_and(|left| Rc::ptr_eq(left, u));
+                if is_left {
+                    u_p.borrow_mut().left = v;
+                } else {
+                    u_p.borrow_mut().right = v;
+                }
+                AugNode::update_agg_upward(Some(u_p));
+                Some(root)
+            }
+        }
+    }
+
+    /**
+     * Deletes the node `z` from the tree rooted at `root`.
+     * Returns the new root of the tree, None if the tree became empty.
+     */
+    pub fn tree_delete(root: AugNodeLink<A>, z: AugNodeLink<A>) -> Option<AugNodeLink<A>> {
+        let z_left = z.borrow().left.clone();
+        let z_right = z.borrow().right.clone();
+
+        let new_root = match (z_left, z_right) {
+            (None, z_right) => AugNode::transplant(root, &z, z_right),
+            (z_left, None) => AugNode::transplant(root, &z, z_left),
+            (Some(z_l), Some(z_r)) => {
+                let y = AugNode::minimum_nodelink(Rc::clone(&z_r)); // y is the successor
+                let mut current_root = root;
+                if !Rc::ptr_eq(&y, &z_r) {
+                    let y_right = y.borrow().right.clone();
+                    current_root = AugNode::transplant(current_root, &y, y_right).unwrap();
+                    y.borrow_mut().right = Some(Rc::clone(&z_r));
+                    z_r.borrow_mut().parent = Some(AugNode::downgrade(&y));
+                }
+                y.borrow_mut().left = Some(Rc::clone(&z_l));
+                z_l.borrow_mut().parent = Some(AugNode::downgrade(&y));
+                AugNode::update_agg(&y);
+                AugNode::transplant(current_root, &z, Some(Rc::clone(&y)))
+            }
+        };
+
+        let mut z_mut = z.borrow_mut();
+        z_mut.parent = None;
+        z_mut.left = None;
+        z_mut.right = None;
+        new_root
+    }
+
+    /**
+     * Rotate `x` down to the left, its right child takes its place.
+     * Returns the root of the tree, which changes if `x` was the root.
+     * Does nothing if `x` has no right child.
+     */
+    pub fn rotate_left(root: AugNodeLink<A>, x: &AugNodeLink<A>) -> AugNodeLink<A> {
+        let Some(y) = x.borrow().right.clone() else {
+            return root;
+        };
+        // y's left subtree becomes x's right subtree
+        let y_left = y.borrow_mut().left.take();
+        if let Some(y_l) = &y_left {
+            y_l.borrow_mut().parent = Some(AugNode::downgrade(x));
+        }
+        x.borrow_mut().right = y_left;
+
+        let new_root = AugNode::transplant(root, x, Some(Rc::clone(&y))).unwrap();
+        y.borrow_mut().left = Some(Rc::clone(x));
+        x.borrow_mut().parent = Some(AugNode::downgrade(&y));
+        // x is now below y, fix it first then everything above
+        AugNode::update_agg(x);
+        AugNode::update_agg_upward(Some(y));
+        new_root
+    }
+
+    /**
+     * Rotate `y` down to the right, its left child takes its place.
+     * Returns the root of the tree, which changes if `y` was the root.
+     * Does nothing if `y` has no left child.
+     */
+    pub fn rotate_right(root: AugNodeLink<A>, y: &AugNodeLink<A>) -> AugNodeLink<A> {
+        let Some(x) = y.borrow().left.clone() else {
+            return root;
+        };
+        // x's right subtree becomes y's left subtree
+        let x_right = x.borrow_mut().right.take();
+        if let Some(x_r) = &x_right {
+            x_r.borrow_mut().parent = Some(AugNode::downgrade(y));
+        }
+        y.borrow_mut().left = x_right;
+
+        let new_root = AugNode::transplant(root, y, Some(Rc::clone(&x))).unwrap();
+        x.borrow_mut().right = Some(Rc::clone(y));
+        y.borrow_mut().parent = Some(AugNode::downgrade(&x));
+        AugNode::update_agg(y);
+        AugNode::update_agg_upward(Some(x));
+        new_root
+    }
+
+    /**
+     * Aggregate of the keys k with a <= k <= b, None if there is no such key
+     * Only the two search paths for `a` and `b` are walked, whole subtrees inside the range use their cached aggregate
+     */
+    pub fn aggregate_range(root: &AugNodeLink<A>, a: i32, b: i32) -> Option<A::Agg> {
+        // find the split node, the first one whose key lies inside [a, b]
+        let mut current = Some(Rc::clone(root));
+        while let Some(node) = current {
+            let key = node.borrow().key;
+            if key < a {
+                current = node.borrow().right.clone();
+            } else if key > b {
+                current = node.borrow().left.clone();
+            } else {
+                let node_ref = node.borrow();
+                let left = AugNode::aggregate_from(node_ref.left.clone(), a);
+                let right = AugNode::aggregate_up_to(node_ref.right.clone(), b);
+                return Some(A::combine(left.as_ref(), key, right.as_ref()));
+            }
+        }
+        None
+    }
+
+    //aggregate of the keys >= a in the subtree, walking down the left boundary
+    fn aggregate_from(node: Option<AugNodeLink<A>>, a: i32) -> Option<A::Agg> {
+        //pieces in descending key order (pushed going down-left), each one a key and the aggregate of the subtree right after it
+        let mut pieces: Vec<(i32, Option<A::Agg>)> = Vec::new();
+        let mut current = node;
+        while let Some(c_node) = current {
+            let c_ref = c_node.borrow();
+            if c_ref.key < a {
+                current = c_ref.right.clone();
+            } else {
+                let right = c_ref.right.as_ref().map(|r| r.borrow().agg.clone());
+                pieces.push((c_ref.key, right));
+                current = c_ref.left.clone();
+            }
+        }
+        //the deepest piece holds the smallest keys, fold from there
+        let mut result: Option<A::Agg> = None;
+        for (key, right) in pieces.into_iter().rev() {
+            result = Some(A::combine(result.as_ref(), key, right.as_ref()));
+        }
+        result
+    }
+
+    //aggregate of the keys <= b in the subtree, walking down the right boundary
+    fn aggregate_up_to(node: Option<AugNodeLink<A>>, b: i32) -> Option<A::Agg> {
+        //pieces in ascending key order (pushed going down-right), each one the aggregate of the subtree right before a key and that key
+        let mut pieces: Vec<(Option<A::Agg>, i32)> = Vec::new();
+        let mut current = node;
+        while let Some(c_node) = current {
+            let c_ref = c_node.borrow();
+            if c_ref.key > b {
+                current = c_ref.left.clone();
+            } else {
+                let left = c_ref.left.as_ref().map(|l| l.borrow().agg.clone());
+                pieces.push((left, c_ref.key));
+                current = c_ref.right.clone();
+            }
+        }
+        //the deepest piece holds the largest keys, fold from there
+        let mut result: Option<A::Agg> = None;
+        for (left, key) in pieces.into_iter().rev() {
+            result = Some(A::combine(left.as_ref(), key, result.as_ref()));
+        }
+        result
+    }
+}
@@ -1,7 +1,27 @@
 use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::fmt;
 use std::rc::{Rc, Weak};
 pub type BstNodeLink = Rc<RefCell<BstNode>>;
 pub type WeakBstNodeLink = Weak<RefCell<BstNode>>;
+//exclusive (lower, upper) bounds on the keys of a subtree, None when unbounded
+pub(crate) type KeyBounds = (Option<i32>, Option<i32>);
+
+//the node given to try_tree_delete belongs to another tree, nothing was deleted
+#[derive(Debug)]
+pub struct ForeignNode {
+    //the root passed in, given back untouched
+    pub root: BstNodeLink,
+    pub key: Option<i32>,
+}
+
+impl fmt::Display for ForeignNode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "node {:?} is not part of this tree, nothing was deleted", self.key)
+    }
+}
+
+impl std::error::Error for ForeignNode {}
 
 //this package implement BST wrapper
 #[derive(Debug, Clone)]
@@ -12,6 +32,70 @@ pub struct BstNode {
     pub right: Option<BstNodeLink>,
 }
 
+/**
+ * Drop children iteratively, the default recursive drop overflows the stack on degenerate trees
+ */
+impl Drop for BstNode {
+    fn drop(&mut self) {
+        let mut stack: Vec<BstNodeLink> = Vec::new();
+        stack.extend(self.left.take());
+        stack.extend(self.right.take());
+        while let Some(node) = stack.pop() {
+            //only tear down nodes nobody else holds
+            if let Ok(cell) = Rc::try_unwrap(node) {
+                let mut child = cell.into_inner();
+                stack.extend(child.left.take());
+                stack.extend(child.right.take());
+            }
+        }
+    }
+}
+
+/**
+ * Which node replaces a deleted node that has two children.
+ * Always picking the successor skews the tree over many delete/insert cycles,
+ * Alternating and Random spread the replacements over both subtrees.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeleteStrategy {
+    Successor,
+    Predecessor,
+    //flipped after every two children deletion
+    Alternating { next_predecessor: bool },
+    //xorshift state, advanced after every two children deletion
+    Random { state: u64 },
+}
+
+impl DeleteStrategy {
+    pub fn alternating() -> Self {
+        DeleteStrategy::Alternating { next_predecessor: false }
+    }
+
+    pub fn random(seed: u64) -> Self {
+        //xorshift gets stuck at zero
+        DeleteStrategy::Random { state: seed.max(1) }
+    }
+
+    //decide for the current deletion and advance the state for the next one
+    fn next_is_predecessor(&mut self) -> bool {
+        match self {
+            DeleteStrategy::Successor => false,
+            DeleteStrategy::Predecessor => true,
+            DeleteStrategy::Alternating { next_predecessor } => {
+                let current = *next_predecessor;
+                *next_predecessor = !current;
+                current
+            }
+            DeleteStrategy::Random { state } => {
+                *state ^= *state << 13;
+                *state ^= *state >> 7;
+                *state ^= *state << 17;
+                *state & 1 == 1
+            }
+        }
+    }
+}
+
 impl BstNode {
     //private interface
     fn new(key: i32) -> Self {
@@ -25,18 +109,43 @@ impl BstNode {
 
     pub fn new_bst_nodelink(value: i32) -> BstNodeLink {
         let currentnode = BstNode::new(value);
-        let currentlink = Rc::new(RefCell::new(currentnode));
-        currentlink
+        Rc::new(RefCell::new(currentnode))
     }
 
     /**
      * Get a copy of node link
+     * Shallow: the children are still shared with self, use deep_clone for an independent tree
      */
     #[allow(dead_code)] 
     pub fn get_bst_nodelink_copy(&self) -> BstNodeLink {
         Rc::new(RefCell::new(self.clone()))
     }
 
+    /**
+     * Copy the whole tree rooted at `root` into fresh nodes (Iterative version)
+     * Every parent link points into the copy, the copied root has no parent
+     */
+    pub fn deep_clone(root: &BstNodeLink) -> BstNodeLink {
+        let new_root = BstNode::new_bst_nodelink(root.borrow().key.unwrap());
+        //pairs of (original node, its copy) whose children are still to be copied
+        let mut stack: Vec<(BstNodeLink, BstNodeLink)> = vec![(Rc::clone(root), Rc::clone(&new_root))];
+
+        while let Some((original, copy)) = stack.pop() {
+            let original_ref = original.borrow();
+            if let Some(left) = &original_ref.left {
+                let left_copy = BstNode::new_with_parent(&copy, left.borrow().key.unwrap());
+                copy.borrow_mut().left = Some(Rc::clone(&left_copy));
+                stack.push((Rc::clone(left), left_copy));
+            }
+            if let Some(right) = &original_ref.right {
+                let right_copy = BstNode::new_with_parent(&copy, right.borrow().key.unwrap());
+                copy.borrow_mut().right = Some(Rc::clone(&right_copy));
+                stack.push((Rc::clone(right), right_copy));
+            }
+        }
+        new_root
+    }
+
     fn downgrade(node: &BstNodeLink) -> WeakBstNodeLink {
         Rc::<RefCell<BstNode>>::downgrade(node)
     }
@@ -45,11 +154,11 @@ impl BstNode {
     fn new_with_parent(parent: &BstNodeLink, value: i32) -> BstNodeLink {
         let mut currentnode = BstNode::new(value);
         currentnode.parent = Some(BstNode::downgrade(parent));
-        let currentlink = Rc::new(RefCell::new(currentnode));
-        currentlink
+        Rc::new(RefCell::new(currentnode))
     }
 
     //add new left child, set the parent to current_node_link
+    #[allow(dead_code)]
     pub fn add_left_child(&mut self, current_node_link: &BstNodeLink, value: i32) {
         let new_node = BstNode::new_with_parent(current_node_link, value);
         self.left = Some(new_node);
@@ -61,6 +170,32 @@ impl BstNode {
         self.right = Some(new_node);
     }
 
+    /**
+     * Attach `key` as a child of `parent` while rebuilding a tree top-down, checking the BST ordering.
+     * `bounds` are the exclusive key bounds of the subtree of `parent`,
+     * returns the new child with the bounds of its own subtree, or why `key` can't go there.
+     */
+    pub(crate) fn attach_checked(
+        parent: &BstNodeLink,
+        bounds: KeyBounds,
+        is_left: bool,
+        key: i32,
+    ) -> Result<(BstNodeLink, KeyBounds), String> {
+        let parent_key = parent.borrow().key.unwrap();
+        let (low, high) = bounds;
+        let (low, high) = if is_left { (low, Some(parent_key)) } else { (Some(parent_key), high) };
+        if low.is_some_and(|l| key <= l) || high.is_some_and(|h| key >= h) {
+            return Err(format!("key {} breaks the BST ordering below {}", key, parent_key));
+        }
+        let child = BstNode::new_with_parent(parent, key);
+        if is_left {
+            parent.borrow_mut().left = Some(Rc::clone(&child));
+        } else {
+            parent.borrow_mut().right = Some(Rc::clone(&child));
+        }
+        Ok((child, (low, high)))
+    }
+
     //search the current tree which node fit the value (Iterative version)
     pub fn tree_search(mut current_node_link: BstNodeLink, value: &i32) -> Option<BstNodeLink> {
         loop {
@@ -90,7 +225,6 @@ impl BstNode {
     /**seek minimum by recurs
      * in BST minimum always on the left
      */
-
     // Revised minimum function that takes BstNodeLink
     pub fn minimum_nodelink(mut node: BstNodeLink) -> BstNodeLink { // Removed mut
         loop {
@@ -118,14 +252,17 @@ impl BstNode {
 
 
     /**
-     * Return the root of a node, return self if not exist
+     * Return the root of a node, return self if not exist (Iterative version)
      */
     pub fn get_root(node: &BstNodeLink) -> BstNodeLink {
-        let parent = BstNode::upgrade_weak_to_strong(node.borrow().parent.clone());
-        if parent.is_none() {
-            return node.clone();
+        let mut current = Rc::clone(node);
+        loop {
+            let parent = BstNode::upgrade_weak_to_strong(current.borrow().parent.clone());
+            match parent {
+                Some(parent_node) => current = parent_node,
+                None => return current,
+            }
         }
-        BstNode::get_root(&parent.unwrap())
     }
 
     /**
@@ -228,22 +365,26 @@ impl BstNode {
         // y is the parent of z
         z_node.borrow_mut().parent = y.clone().map(|node| BstNode::downgrade(&node));
 
-        if y.is_none() {
+        match y {
             // z is the root
-            z_node
-        } else if z_key < y.as_ref().unwrap().borrow().key.unwrap() {
-            // z is the left child
-            y.unwrap().borrow_mut().left = Some(z_node.clone()); // Use clone
-            root.unwrap() // Root doesn't change if y exists
-        } else {
-            // z is the right child
-            y.unwrap().borrow_mut().right = Some(z_node.clone()); // Use clone
-             root.unwrap() // Root doesn't change if y exists
+            None => z_node,
+            Some(y_node) => {
+                if z_key < y_node.borrow().key.unwrap() {
+                    // z is the left child
+                    y_node.borrow_mut().left = Some(z_node.clone()); // Use clone
+                } else {
+                    // z is the right child
+                    y_node.borrow_mut().right = Some(z_node.clone()); // Use clone
+                }
+                root.unwrap() // Root doesn't change if y exists
+            }
         }
     }
 
 
     /**
+     * Replace the subtree rooted at `u` with the subtree rooted at `v`.
+     * Returns the root of the tree, which changes only if `u` was the root.
      */
     pub fn transplant(root: BstNodeLink, u: BstNodeLink, v: Option<BstNodeLink>) -> BstNodeLink { // Removed mut root
         let u_parent = BstNode::upgrade_weak_to_strong(u.borrow().parent.clone());
@@ -293,8 +434,50 @@ impl BstNode {
     /**
      * Deletes the node `z` from the BST rooted at `root`.
      * Returns the new root of the tree.
+     * If `z` doesn't belong to the tree of `root` nothing is deleted and `root` is returned as is,
+     * use try_tree_delete to tell that case apart.
      */
     pub fn tree_delete(root: BstNodeLink, z: BstNodeLink) -> BstNodeLink { // Removed mut root
+        BstNode::try_tree_delete(root, z).unwrap_or_else(|foreign| foreign.root)
+    }
+
+    /**
+     * Same as tree_delete, but `strategy` decides whether a node with two children
+     * is replaced by its successor or by its predecessor.
+     */
+    pub fn tree_delete_with(root: BstNodeLink, z: BstNodeLink, strategy: &mut DeleteStrategy) -> BstNodeLink {
+        BstNode::try_tree_delete_with(root, z, strategy).unwrap_or_else(|foreign| foreign.root)
+    }
+
+    /**
+     * Deletes the node `z` from the BST rooted at `root`, returning the new root.
+     * If `z` doesn't belong to the tree of `root`, nothing is deleted and the untouched root is given back in the error.
+     */
+    pub fn try_tree_delete(root: BstNodeLink, z: BstNodeLink) -> Result<BstNodeLink, ForeignNode> {
+        BstNode::try_tree_delete_with(root, z, &mut DeleteStrategy::Successor)
+    }
+
+    /**
+     * Same as try_tree_delete, deleting with the given `strategy`
+     */
+    pub fn try_tree_delete_with(
+        root: BstNodeLink,
+        z: BstNodeLink,
+        strategy: &mut DeleteStrategy,
+    ) -> Result<BstNodeLink, ForeignNode> {
+        // Relinking a node of another tree would corrupt both trees
+        if !Rc::ptr_eq(&BstNode::get_root(&z), &root) {
+            let key = z.borrow().key;
+            return Err(ForeignNode { root, key });
+        }
+        Ok(BstNode::delete_unchecked(root, z, strategy))
+    }
+
+    /**
+     * The deletion itself, `z` must belong to the tree of `root`.
+     * Callers that found `z` from `root` skip the walk up to the root this way.
+     */
+    fn delete_unchecked(root: BstNodeLink, z: BstNodeLink, strategy: &mut DeleteStrategy) -> BstNodeLink {
         let z_borrowed = z.borrow();
         let z_left = z_borrowed.left.clone();
         let z_right = z_borrowed.right.clone();
@@ -307,6 +490,29 @@ impl BstNode {
         } else if z_right.is_none() {
             // Case 2: z has no right child
             BstNode::transplant(root, Rc::clone(&z), z_left)
+        } else if strategy.next_is_predecessor() {
+            // Case 3, mirrored: z has two children, replace it by its predecessor
+            let y = BstNode::maximum_nodelink(z_left.clone().unwrap()); // y is the predecessor
+            let y_parent = BstNode::upgrade_weak_to_strong(y.borrow().parent.clone());
+
+            if !Rc::ptr_eq(y_parent.as_ref().unwrap(), &z) {
+                // y is not z's left child, lift y's left subtree into y's place first
+                let y_left = y.borrow().left.clone();
+                BstNode::transplant(root.clone(), Rc::clone(&y), y_left);
+
+                y.borrow_mut().left = z_left.clone();
+                if let Some(z_l) = z_left {
+                    z_l.borrow_mut().parent = Some(BstNode::downgrade(&y));
+                }
+            }
+
+            let new_root = BstNode::transplant(root, Rc::clone(&z), Some(Rc::clone(&y)));
+
+            let z_right = z.borrow().right.clone().unwrap();
+            y.borrow_mut().right = Some(z_right.clone());
+            z_right.borrow_mut().parent = Some(BstNode::downgrade(&y));
+
+            new_root
         } else {
             // Case 3: z has two children
             let y = BstNode::minimum_nodelink(z_right.clone().unwrap()); // y is the successor
@@ -315,7 +521,7 @@ impl BstNode {
             let y_parent = BstNode::upgrade_weak_to_strong(y.borrow().parent.clone());
 
             // Check if y is not z's direct right child
-            if !Rc::ptr_eq(&y_parent.as_ref().unwrap(), &z) {
+            if !Rc::ptr_eq(y_parent.as_ref().unwrap(), &z) {
                 // Case 3a: y is not z's right child
                  let y_right = y.borrow().right.clone(); // Get y's right child
                  BstNode::transplant(root.clone(), Rc::clone(&y), y_right); // Added semicolon
@@ -342,6 +548,353 @@ impl BstNode {
         }
     }
 
+    /**
+     * Build a balanced tree from keys given in strictly ascending order (Iterative version)
+     * Return None if `keys` is empty
+     */
+    pub fn from_sorted(keys: &[i32]) -> Option<BstNodeLink> {
+        if keys.is_empty() {
+            return None;
+        }
+        let mid = keys.len() / 2;
+        let root = BstNode::new_bst_nodelink(keys[mid]);
+        //(range of keys still to place, node they hang from, whether they go to its left)
+        let mut stack: Vec<(usize, usize, BstNodeLink, bool)> = vec![
+            (0, mid, Rc::clone(&root), true),
+            (mid + 1, keys.len(), Rc::clone(&root), false),
+        ];
+        while let Some((lo, hi, parent, is_left)) = stack.pop() {
+            if lo >= hi {
+                continue;
+            }
+            let mid = lo + (hi - lo) / 2;
+            let node = BstNode::new_with_parent(&parent, keys[mid]);
+            if is_left {
+                parent.borrow_mut().left = Some(Rc::clone(&node));
+            } else {
+                parent.borrow_mut().right = Some(Rc::clone(&node));
+            }
+            stack.push((lo, mid, Rc::clone(&node), true));
+            stack.push((mid + 1, hi, node, false));
+        }
+        Some(root)
+    }
+
+    /**
+     * Collect every node of the tree rooted at `root` in key order (Iterative version)
+     */
+    pub fn inorder_nodelinks(root: &BstNodeLink) -> Vec<BstNodeLink> {
+        let mut result: Vec<BstNodeLink> = Vec::new();
+        let mut stack: Vec<BstNodeLink> = Vec::new();
+        let mut current = Some(Rc::clone(root));
+
+        while current.is_some() || !stack.is_empty() {
+            // go as far left as possible, remembering the way back
+            while let Some(node) = current {
+                current = node.borrow().left.clone();
+                stack.push(node);
+            }
+            let node = stack.pop().unwrap();
+            current = node.borrow().right.clone();
+            result.push(node);
+        }
+        result
+    }
+
+    /**
+     * Delete `z` from the tree and cut every link it still holds.
+     * Unlike tree_delete, return None when the last node of the tree is removed.
+     */
+    fn unlink(root: BstNodeLink, z: &BstNodeLink, strategy: &mut DeleteStrategy) -> Option<BstNodeLink> {
+        let is_last_node = Rc::ptr_eq(&root, z)
+            && z.borrow().left.is_none()
+            && z.borrow().right.is_none();
+        let new_root = if is_last_node {
+            None
+        } else {
+            // z was found from root, so it is known to be part of the tree
+            Some(BstNode::delete_unchecked(root, Rc::clone(z), strategy))
+        };
+
+        let mut z_mut = z.borrow_mut();
+        z_mut.parent = None;
+        z_mut.left = None;
+        z_mut.right = None;
+        new_root
+    }
+
+    /**
+     * Search the node holding `key` and delete it.
+     * Returns the new root (None if the tree became empty) and the removed key, None if `key` was not found.
+     */
+    pub fn remove(root: Option<BstNodeLink>, key: &i32) -> (Option<BstNodeLink>, Option<i32>) {
+        BstNode::remove_with(root, key, &mut DeleteStrategy::Successor)
+    }
+
+    /**
+     * Same as remove, deleting with the given `strategy`
+     */
+    pub fn remove_with(
+        root: Option<BstNodeLink>,
+        key: &i32,
+        strategy: &mut DeleteStrategy,
+    ) -> (Option<BstNodeLink>, Option<i32>) {
+        let Some(r) = root else {
+            return (None, None);
+        };
+        match BstNode::tree_search(Rc::clone(&r), key) {
+            Some(z) => (BstNode::unlink(r, &z, strategy), Some(*key)),
+            None => (Some(r), None),
+        }
+    }
+
+    /**
+     * Delete the node with the smallest key.
+     * Returns the new root and the removed key, None if the tree was empty.
+     */
+    pub fn pop_first(root: Option<BstNodeLink>) -> (Option<BstNodeLink>, Option<i32>) {
+        let Some(r) = root else {
+            return (None, None);
+        };
+        let z = BstNode::minimum_nodelink(Rc::clone(&r));
+        let key = z.borrow().key;
+        (BstNode::unlink(r, &z, &mut DeleteStrategy::Successor), key)
+    }
+
+    /**
+     * Delete the node with the largest key.
+     * Returns the new root and the removed key, None if the tree was empty.
+     */
+    pub fn pop_last(root: Option<BstNodeLink>) -> (Option<BstNodeLink>, Option<i32>) {
+        let Some(r) = root else {
+            return (None, None);
+        };
+        let z = BstNode::maximum_nodelink(Rc::clone(&r));
+        let key = z.borrow().key;
+        (BstNode::unlink(r, &z, &mut DeleteStrategy::Successor), key)
+    }
+
+    /**
+     * Remove every node whose key matches `pred`, walking the tree once in key order.
+     * Returns the new root (None if the tree became empty) and the removed keys in ascending order.
+     */
+    pub fn extract_if<F>(root: Option<BstNodeLink>, mut pred: F) -> (Option<BstNodeLink>, Vec<i32>)
+    where
+        F: FnMut(&i32) -> bool,
+    {
+        let mut removed: Vec<i32> = Vec::new();
+        let nodes = root.as_ref().map(BstNode::inorder_nodelinks).unwrap_or_default();
+        let mut current_root = root;
+
+        // tree_delete relinks nodes instead of copying keys, so the collected handles stay valid
+        for node in nodes {
+            let key = node.borrow().key.unwrap();
+            if !pred(&key) {
+                continue;
+            }
+            if let Some(r) = current_root {
+                current_root = BstNode::unlink(r, &node, &mut DeleteStrategy::Successor);
+                removed.push(key);
+            }
+        }
+        (current_root, removed)
+    }
+
+    /**
+     * Keep only the nodes whose key matches `f`, the rest are unlinked from the tree.
+     * Returns the new root, None if nothing is kept.
+     */
+    pub fn retain<F>(root: Option<BstNodeLink>, mut f: F) -> Option<BstNodeLink>
+    where
+        F: FnMut(&i32) -> bool,
+    {
+        BstNode::extract_if(root, |key| !f(key)).0
+    }
+
+    /**
+     * Remove every node of the tree, returning the keys in ascending order
+     */
+    pub fn drain(root: Option<BstNodeLink>) -> Vec<i32> {
+        BstNode::extract_if(root, |_| true).1
+    }
+
+
+    /**
+     * A leaf is a node without any child
+     */
+    pub fn is_leaf(&self) -> bool {
+        self.left.is_none() && self.right.is_none()
+    }
+
+    /**
+     * Amount of nodes in each level of the tree, starting from the root level (Iterative version)
+     */
+    pub fn level_widths(root: &BstNodeLink) -> Vec<usize> {
+        let mut widths: Vec<usize> = Vec::new();
+        let mut queue: VecDeque<BstNodeLink> = VecDeque::new();
+        queue.push_back(Rc::clone(root));
+
+        while !queue.is_empty() {
+            let width = queue.len();
+            widths.push(width);
+            //only pop the nodes of the current level, their children make the next level
+            for _ in 0..width {
+                let node = queue.pop_front().unwrap();
+                let node_ref = node.borrow();
+                if let Some(left) = &node_ref.left {
+                    queue.push_back(Rc::clone(left));
+                }
+                if let Some(right) = &node_ref.right {
+                    queue.push_back(Rc::clone(right));
+                }
+            }
+        }
+        widths
+    }
+
+    /**
+     * Height of the tree, counted in edges from root to the deepest leaf
+     * A single node tree has height 0
+     */
+    pub fn height(root: &BstNodeLink) -> usize {
+        BstNode::level_widths(root).len() - 1
+    }
+
+    /**
+     * Amount of nodes in the tree rooted at `root`
+     */
+    pub fn size(root: &BstNodeLink) -> usize {
+        BstNode::level_widths(root).iter().sum()
+    }
+
+    /**
+     * Distance in edges from `node` up to the root, following the parent links
+     * The root has depth 0
+     */
+    pub fn depth(node: &BstNodeLink) -> usize {
+        let mut depth = 0;
+        let mut parent = BstNode::upgrade_weak_to_strong(node.borrow().parent.clone());
+        while let Some(p_node) = parent {
+            depth += 1;
+            parent = BstNode::upgrade_weak_to_strong(p_node.borrow().parent.clone());
+        }
+        depth
+    }
+
+    /**
+     * Nodes on the way from the root down to `node`, both included, following the parent links
+     */
+    pub fn path_from_root(node: &BstNodeLink) -> Vec<BstNodeLink> {
+        let mut path: Vec<BstNodeLink> = vec![Rc::clone(node)];
+        let mut parent = BstNode::upgrade_weak_to_strong(node.borrow().parent.clone());
+        while let Some(p_node) = parent {
+            parent = BstNode::upgrade_weak_to_strong(p_node.borrow().parent.clone());
+            path.push(p_node);
+        }
+        path.reverse();
+        path
+    }
+
+    //nodes visited while searching `key` starting at `from`, None if the key is not there
+    fn search_path(from: &BstNodeLink, key: &i32) -> Option<Vec<BstNodeLink>> {
+        let mut path: Vec<BstNodeLink> = Vec::new();
+        let mut current = Some(Rc::clone(from));
+        while let Some(node) = current {
+            let node_key = node.borrow().key.unwrap();
+            current = if *key < node_key {
+                node.borrow().left.clone()
+            } else if *key > node_key {
+                node.borrow().right.clone()
+            } else {
+                None
+            };
+            path.push(node);
+            if node_key == *key {
+                return Some(path);
+            }
+        }
+        None
+    }
+
+    /**
+     * Lowest common ancestor of the nodes holding `a` and `b`
+     * It is the first node on the way down where `a` and `b` go to different sides
+     * Return None if one of the keys is not in the tree
+     */
+    pub fn lca(root: &BstNodeLink, a: &i32, b: &i32) -> Option<BstNodeLink> {
+        let mut current = Rc::clone(root);
+        loop {
+            let key = current.borrow().key.unwrap();
+            let next = if *a < key && *b < key {
+                current.borrow().left.clone()
+            } else if *a > key && *b > key {
+                current.borrow().right.clone()
+            } else {
+                break;
+            };
+            current = next?;
+        }
+        // both keys must actually exist below the split node
+        BstNode::search_path(&current, a)?;
+        BstNode::search_path(&current, b)?;
+        Some(current)
+    }
+
+    /**
+     * Nodes on the path from the node holding `a` to the node holding `b`, both included
+     * Return None if one of the keys is not in the tree
+     */
+    pub fn path_between(root: &BstNodeLink, a: &i32, b: &i32) -> Option<Vec<BstNodeLink>> {
+        let ancestor = BstNode::lca(root, a, b)?;
+        let mut path = BstNode::search_path(&ancestor, a)?;
+        let down_to_b = BstNode::search_path(&ancestor, b)?;
+        // climb from a up to the ancestor then go down to b, the ancestor is listed once
+        path.reverse();
+        path.extend(down_to_b.into_iter().skip(1));
+        Some(path)
+    }
+
+    /**
+     * Amount of edges between the nodes holding `a` and `b`
+     * Return None if one of the keys is not in the tree
+     */
+    pub fn distance(root: &BstNodeLink, a: &i32, b: &i32) -> Option<usize> {
+        BstNode::path_between(root, a, b).map(|path| path.len() - 1)
+    }
+
+    /**
+     * Return true if both trees have the same shape and the same key at every position
+     */
+    pub fn structurally_equal(a: &BstNodeLink, b: &BstNodeLink) -> bool {
+        let mut stack: Vec<(BstNodeLink, BstNodeLink)> = vec![(Rc::clone(a), Rc::clone(b))];
+        while let Some((a_node, b_node)) = stack.pop() {
+            if !BstNode::is_node_match(&a_node, &b_node) {
+                return false;
+            }
+            let (a_ref, b_ref) = (a_node.borrow(), b_node.borrow());
+            for (a_child, b_child) in [(&a_ref.left, &b_ref.left), (&a_ref.right, &b_ref.right)] {
+                match (a_child, b_child) {
+                    (None, None) => {}
+                    (Some(a_c), Some(b_c)) => stack.push((Rc::clone(a_c), Rc::clone(b_c))),
+                    _ => return false,
+                }
+            }
+        }
+        true
+    }
+
+    /**
+     * Return true if both trees hold the same keys, whatever their shape
+     */
+    pub fn same_keys(a: &BstNodeLink, b: &BstNodeLink) -> bool {
+        let a_nodes = BstNode::inorder_nodelinks(a);
+        let b_nodes = BstNode::inorder_nodelinks(b);
+        a_nodes.len() == b_nodes.len()
+            && a_nodes
+                .iter()
+                .zip(b_nodes.iter())
+                .all(|(a_node, b_node)| BstNode::is_node_match(a_node, b_node))
+    }
 
     /**
      * private function return true if node doesn't has parent nor children nor key
@@ -359,7 +912,7 @@ impl BstNode {
                 {
                     return true;
                 }
-                return false;
+                false
             }
         }
     }
@@ -373,10 +926,9 @@ impl BstNode {
         if let Some(node1v) = node1 {
             return node2.is_some_and(|x: BstNodeLink| x.borrow().key == node1v.borrow().key);
         }
-        return false;
+        false
     }
 
-    #[allow(dead_code)] // Not used in the core insert/delete logic
     fn is_node_match(anode: &BstNodeLink, bnode: &BstNodeLink) -> bool {
         anode.borrow().key == bnode.borrow().key
     }
@@ -0,0 +1,161 @@
+//this package implement serde support for BstNode trees, enabled with the `serde` cargo feature
+//two layouts are available:
+//- sorted: the keys in ascending order, loaded back as a balanced tree (the default)
+//- shape: pre-order keys with a null marker for every missing child, loaded back with the exact same shape
+//both layouts validate the BST ordering on load and rebuild every parent link
+
+use crate::structure::bst::{BstNode, BstNodeLink, KeyBounds};
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::rc::Rc;
+
+/**
+ * Serialize/Deserialize a tree as its sorted keys, e.g. [2, 3, 4]
+ * Use it on a field with #[serde(with = "bst_serde::sorted")]
+ */
+pub mod sorted {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(root: &Option<BstNodeLink>, serializer: S) -> Result<S::Ok, S::Error> {
+        let nodes = root.as_ref().map(BstNode::inorder_nodelinks).unwrap_or_default();
+        serializer.collect_seq(nodes.iter().map(|node| node.borrow().key.unwrap()))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<BstNodeLink>, D::Error> {
+        let keys = Vec::<i32>::deserialize(deserializer)?;
+        if let Some(index) = keys.windows(2).position(|pair| pair[0] >= pair[1]) {
+            return Err(D::Error::custom(format!(
+                "keys must be strictly ascending, found {} then {} at index {}",
+                keys[index],
+                keys[index + 1],
+                index + 1
+            )));
+        }
+        Ok(BstNode::from_sorted(&keys))
+    }
+}
+
+/**
+ * Serialize/Deserialize a tree in pre-order with null markers, e.g. [3, 2, null, null, 4, null, null]
+ * Use it on a field with #[serde(with = "bst_serde::shape")]
+ */
+pub mod shape {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(root: &Option<BstNodeLink>, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut tokens: Vec<Option<i32>> = Vec::new();
+        let mut stack: Vec<Option<BstNodeLink>> = vec![root.clone()];
+        while let Some(slot) = stack.pop() {
+            match slot {
+                None => tokens.push(None),
+                Some(node) => {
+                    let node_ref = node.borrow();
+                    tokens.push(node_ref.key);
+                    //right first so the left subtree is written first
+                    stack.push(node_ref.right.clone());
+                    stack.push(node_ref.left.clone());
+                }
+            }
+        }
+        serializer.collect_seq(tokens)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<BstNodeLink>, D::Error> {
+        let tokens = Vec::<Option<i32>>::deserialize(deserializer)?;
+        rebuild(&tokens).map_err(D::Error::custom)
+    }
+
+    //a node whose children are still to be read, with the exclusive bounds its subtree keys must respect
+    struct Pending {
+        node: BstNodeLink,
+        bounds: KeyBounds,
+        left_done: bool,
+    }
+
+    fn rebuild(tokens: &[Option<i32>]) -> Result<Option<BstNodeLink>, String> {
+        let Some(first) = tokens.first() else {
+            return Err("empty input, an empty tree is written as [null]".to_string());
+        };
+        let Some(root_key) = first else {
+            if tokens.len() > 1 {
+                return Err("unexpected entry at index 1 after an empty tree".to_string());
+            }
+            return Ok(None);
+        };
+
+        let root = BstNode::new_bst_nodelink(*root_key);
+        let mut stack: Vec<Pending> = vec![Pending {
+            node: Rc::clone(&root),
+            bounds: (None, None),
+            left_done: false,
+        }];
+
+        for (index, token) in tokens.iter().enumerate().skip(1) {
+            let Some(top) = stack.last_mut() else {
+                return Err(format!("unexpected entry at index {}, the tree is already complete", index));
+            };
+            let parent = Rc::clone(&top.node);
+            let bounds = top.bounds;
+            let is_left = !top.left_done;
+            if is_left {
+                top.left_done = true;
+            } else {
+                stack.pop();
+            }
+
+            if let Some(key) = token {
+                let (child, bounds) = BstNode::attach_checked(&parent, bounds, is_left, *key)
+                    .map_err(|message| format!("{} at index {}", message, index))?;
+                stack.push(Pending {
+                    node: child,
+                    bounds,
+                    left_done: false,
+                });
+            }
+        }
+
+        if !stack.is_empty() {
+            return Err(format!(
+                "input ends early, {} node(s) still miss their children markers",
+                stack.len()
+            ));
+        }
+        Ok(Some(root))
+    }
+}
+
+/**
+ * A whole tree serialized as its sorted keys, the default layout
+ */
+#[derive(Debug, Clone, Default)]
+pub struct SerdeBst(pub Option<BstNodeLink>);
+
+impl Serialize for SerdeBst {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        sorted::serialize(&self.0, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for SerdeBst {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        sorted::deserialize(deserializer).map(SerdeBst)
+    }
+}
+
+/**
+ * A whole tree serialized with its exact shape
+ */
+#[derive(Debug, Clone, Default)]
+pub struct ShapedBst(pub Option<BstNodeLink>);
+
+impl Serialize for ShapedBst {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        shape::serialize(&self.0, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for ShapedBst {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        shape::deserialize(deserializer).map(ShapedBst)
+    }
+}
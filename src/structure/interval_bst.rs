@@ -0,0 +1,234 @@
+use std::cell::RefCell;
+use std::rc::{Rc, Weak};
+pub type IntervalNodeLink = Rc<RefCell<IntervalNode>>;
+pub type WeakIntervalNodeLink = Weak<RefCell<IntervalNode>>;
+
+//this package implement an interval tree on top of the BST
+//nodes are ordered by interval start (then end), and each node also keeps the max end of its subtree
+//intervals are closed: [start, end]
+#[derive(Debug, Clone)]
+pub struct IntervalNode {
+    pub start: i32,
+    pub end: i32,
+    pub max_end: i32,
+    pub parent: Option<WeakIntervalNodeLink>,
+    pub left: Option<IntervalNodeLink>,
+    pub right: Option<IntervalNodeLink>,
+}
+
+impl IntervalNode {
+    //private interface
+    fn new(start: i32, end: i32) -> Self {
+        IntervalNode {
+            start,
+            end,
+            max_end: end,
+            parent: None,
+            left: None,
+            right: None,
+        }
+    }
+
+    pub fn new_interval_nodelink(start: i32, end: i32) -> IntervalNodeLink {
+        Rc::new(RefCell::new(IntervalNode::new(start, end)))
+    }
+
+    fn downgrade(node: &IntervalNodeLink) -> WeakIntervalNodeLink {
+        Rc::<RefCell<IntervalNode>>::downgrade(node)
+    }
+
+    fn upgrade_weak_to_strong(node: Option<WeakIntervalNodeLink>) -> Option<IntervalNodeLink> {
+        node.and_then(|weak_node| weak_node.upgrade())
+    }
+
+    fn interval(&self) -> (i32, i32) {
+        (self.start, self.end)
+    }
+
+    //recompute max_end of `node` from its own end and its children
+    fn update_max_end(node: &IntervalNodeLink) {
+        let mut node_mut = node.borrow_mut();
+        let mut max_end = node_mut.end;
+        for child in [&node_mut.left, &node_mut.right].into_iter().flatten() {
+            max_end = max_end.max(child.borrow().max_end);
+        }
+        node_mut.max_end = max_end;
+    }
+
+    //recompute max_end from `node` up to the root
+    fn update_max_end_upward(node: Option<IntervalNodeLink>) {
+        let mut current = node;
+        while let Some(c_node) = current {
+            IntervalNode::update_max_end(&c_node);
+            current = IntervalNode::upgrade_weak_to_strong(c_node.borrow().parent.clone());
+        }
+    }
+
+    //search the node holding exactly [start, end] (Iterative version)
+    pub fn tree_search(root: &IntervalNodeLink, start: i32, end: i32) -> Option<IntervalNodeLink> {
+        let mut current = Some(Rc::clone(root));
+        while let Some(node) = current {
+            let interval = node.borrow().interval();
+            if interval == (start, end) {
+                return Some(node);
+            }
+            current = if (start, end) < interval {
+                node.borrow().left.clone()
+            } else {
+                node.borrow().right.clone()
+            };
+        }
+        None
+    }
+
+    pub fn minimum_nodelink(mut node: IntervalNodeLink) -> IntervalNodeLink {
+        loop {
+            let left_child = node.borrow().left.clone();
+            match left_child {
+                Some(left_node) => node = left_node,
+                None => return node,
+            }
+        }
+    }
+
+    /**
+     * Insert the interval [start, end] into the tree rooted at `root`.
+     * Returns the updated root of the tree, an interval already in the tree is not inserted twice.
+     */
+    pub fn tree_insert(root: Option<IntervalNodeLink>, start: i32, end: i32) -> IntervalNodeLink {
+        let z_node = IntervalNode::new_interval_nodelink(start, end);
+        let mut y: Option<IntervalNodeLink> = None; // trailing pointer
+        let mut x = root.clone();
+
+        while let Some(current_x) = x {
+            let interval = current_x.borrow().interval();
+            if interval == (start, end) {
+                println!("Interval [{}, {}] already exists, not inserting.", start, end);
+                return root.unwrap();
+            }
+            x = if (start, end) < interval {
+                current_x.borrow().left.clone()
+            } else {
+                current_x.borrow().right.clone()
+            };
+            y = Some(current_x);
+        }
+
+        match y {
+            // z is the root
+            None => z_node,
+            Some(y_node) => {
+                z_node.borrow_mut().parent = Some(IntervalNode::downgrade(&y_node));
+                if (start, end) < y_node.borrow().interval() {
+                    y_node.borrow_mut().left = Some(z_node);
+                } else {
+                    y_node.borrow_mut().right = Some(z_node);
+                }
+                // every ancestor of z may now cover a larger end
+                IntervalNode::update_max_end_upward(Some(y_node));
+                root.unwrap()
+            }
+        }
+    }
+
+    /**
+     * Replace the subtree rooted at `u` with the subtree rooted at `v`, then fix max_end above it.
+     * Returns the root of the tree, None if `u` was the root and `v` is None.
+     */
+    pub fn transplant(
+        root: IntervalNodeLink,
+        u: &IntervalNodeLink,
+        v: Option<IntervalNodeLink>,
+    ) -> Option<IntervalNodeLink> {
+        let u_parent = IntervalNode::upgrade_weak_to_strong(u.borrow().parent.clone());
+        if let Some(v_node) = &v {
+            v_node.borrow_mut().parent = u_parent.as_ref().map(IntervalNode::downgrade);
+        }
+
+        match u_parent {
+            None => v,
+            Some(u_p) => {
+                let is_left = u_p.borrow().left.as_ref().is_some_and(|left| Rc::ptr_eq(left, u));
+                if is_left {
+                    u_p.borrow_mut().left = v;
+                } else {
+                    u_p.borrow_mut().right = v;
+                }
+                IntervalNode::update_max_end_upward(Some(u_p));
+                Some(root)
+            }
+        }
+    }
+
+    /**
+     * Deletes the node `z` from the tree rooted at `root`.
+     * Returns the new root of the tree, None if the tree became empty.
+     */
+    pub fn tree_delete(root: IntervalNodeLink, z: IntervalNodeLink) -> Option<IntervalNodeLink> {
+        let z_left = z.borrow().left.clone();
+        let z_right = z.borrow().right.clone();
+
+        let new_root = match (z_left, z_right) {
+            (None, z_right) => IntervalNode::transplant(root, &z, z_right),
+            (z_left, None) => IntervalNode::transplant(root, &z, z_left),
+            (Some(z_l), Some(z_r)) => {
+                let y = IntervalNode::minimum_nodelink(Rc::clone(&z_r)); // y is the successor
+                let mut current_root = root;
+                if !Rc::ptr_eq(&y, &z_r) {
+                    let y_right = y.borrow().right.clone();
+                    current_root = IntervalNode::transplant(current_root, &y, y_right).unwrap();
+                    y.borrow_mut().right = Some(Rc::clone(&z_r));
+                    z_r.borrow_mut().parent = Some(IntervalNode::downgrade(&y));
+                }
+                y.borrow_mut().left = Some(Rc::clone(&z_l));
+                z_l.borrow_mut().parent = Some(IntervalNode::downgrade(&y));
+                // y took new children, its max_end must be known before fixing the path above z
+                IntervalNode::update_max_end(&y);
+                IntervalNode::transplant(current_root, &z, Some(Rc::clone(&y)))
+            }
+        };
+
+        let mut z_mut = z.borrow_mut();
+        z_mut.parent = None;
+        z_mut.left = None;
+        z_mut.right = None;
+        new_root
+    }
+
+    /**
+     * All intervals of the tree overlapping [start, end], ordered by start
+     * Subtrees whose max_end is below `start` are skipped, as are right subtrees starting after `end`
+     */
+    pub fn overlapping(root: &IntervalNodeLink, start: i32, end: i32) -> Vec<(i32, i32)> {
+        let mut found: Vec<(i32, i32)> = Vec::new();
+        let mut stack: Vec<IntervalNodeLink> = vec![Rc::clone(root)];
+
+        while let Some(node) = stack.pop() {
+            let node_ref = node.borrow();
+            if node_ref.max_end < start {
+                continue;
+            }
+            if node_ref.start <= end && start <= node_ref.end {
+                found.push(node_ref.interval());
+            }
+            if let Some(left) = &node_ref.left {
+                stack.push(Rc::clone(left));
+            }
+            // every interval on the right starts at or after this one
+            if node_ref.start <= end {
+                if let Some(right) = &node_ref.right {
+                    stack.push(Rc::clone(right));
+                }
+            }
+        }
+        found.sort();
+        found
+    }
+
+    /**
+     * All intervals of the tree containing `point`, ordered by start
+     */
+    pub fn stabbing(root: &IntervalNodeLink, point: i32) -> Vec<(i32, i32)> {
+        IntervalNode::overlapping(root, point, point)
+    }
+}
@@ -0,0 +1,230 @@
+//this package implement a read-only BST stored as an implicit array in Eytzinger (BFS) order
+//node k has children 2k and 2k + 1 (1-based), so there are no pointers to store and the file is just the keys
+//the file is memory-mapped when the `mmap` feature is on, otherwise (or if mapping fails) it is read into memory
+//keys are read straight from the bytes, nothing is deserialised into nodes
+//
+//file layout, all integers little endian:
+//- magic "BEYT" (4 bytes), format version (1 byte), 3 reserved bytes
+//- key count (8 bytes)
+//- keys (4 bytes each) in Eytzinger order
+
+use crate::structure::bst::{BstNode, BstNodeLink};
+use std::fs::File;
+use std::io::{self, BufWriter, Read, Write};
+use std::path::Path;
+
+const MAGIC: &[u8; 4] = b"BEYT";
+const VERSION: u8 = 1;
+const HEADER_LEN: usize = 16;
+const KEY_LEN: usize = 4;
+
+enum Storage {
+    #[cfg(feature = "mmap")]
+    Mapped(memmap2::Mmap),
+    InMemory(Vec<u8>),
+}
+
+pub struct MappedBst {
+    storage: Storage,
+    len: usize,
+}
+
+fn invalid_data(reason: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, reason)
+}
+
+/**
+ * Visit the implicit tree indices 1..=len in key order (Iterative version)
+ */
+fn inorder_indices(len: usize) -> impl Iterator<Item = usize> {
+    let mut k = leftmost(1, len);
+    std::iter::from_fn(move || {
+        if k == 0 {
+            return None;
+        }
+        let current = k;
+        if 2 * k < len {
+            //right child exists, go to the leftmost node of the right subtree
+            k = leftmost(2 * k + 1, len);
+        } else {
+            //climb while coming from a right child, then once more
+            while k & 1 == 1 {
+                k >>= 1;
+            }
+            k >>= 1;
+        }
+        Some(current)
+    })
+}
+
+fn leftmost(mut k: usize, len: usize) -> usize {
+    if k > len {
+        return 0;
+    }
+    while 2 * k <= len {
+        k *= 2;
+    }
+    k
+}
+
+impl MappedBst {
+    /**
+     * Write `sorted_keys` (strictly ascending) to `path` in Eytzinger order
+     */
+    pub fn write_file<P: AsRef<Path>>(path: P, sorted_keys: &[i32]) -> io::Result<()> {
+        if let Some(index) = sorted_keys.windows(2).position(|pair| pair[0] >= pair[1]) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("keys must be strictly ascending, found {} then {}", sorted_keys[index], sorted_keys[index + 1]),
+            ));
+        }
+        //the i-th smallest key goes to the i-th index of the in-order walk
+        let mut layout = vec![0i32; sorted_keys.len()];
+        for (k, &key) in inorder_indices(sorted_keys.len()).zip(sorted_keys) {
+            layout[k - 1] = key;
+        }
+
+        let mut writer = BufWriter::new(File::create(path)?);
+        writer.write_all(MAGIC)?;
+        writer.write_all(&[VERSION, 0, 0, 0])?;
+        writer.write_all(&(sorted_keys.len() as u64).to_le_bytes())?;
+        for key in layout {
+            writer.write_all(&key.to_le_bytes())?;
+        }
+        writer.flush()
+    }
+
+    /**
+     * Write the keys of the tree rooted at `root` to `path`
+     */
+    pub fn write_tree<P: AsRef<Path>>(path: P, root: &BstNodeLink) -> io::Result<()> {
+        let keys: Vec<i32> = BstNode::inorder_nodelinks(root)
+            .iter()
+            .map(|node| node.borrow().key.unwrap())
+            .collect();
+        MappedBst::write_file(path, &keys)
+    }
+
+    /**
+     * Open a file written by write_file, memory-mapped if possible
+     * With the `mmap` feature the returned tree reads the file itself: the file must not be truncated
+     * or rewritten, by this process or another one, until the tree is dropped, or reads will see torn
+     * data or crash. Use open_in_memory when the file may change while the tree is alive.
+     */
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        #[cfg(feature = "mmap")]
+        {
+            let file = File::open(&path)?;
+            // SAFETY: the mapping is read-only, the file must not be truncated or rewritten while it is open
+            if let Ok(map) = unsafe { memmap2::Mmap::map(&file) } {
+                return MappedBst::from_storage(Storage::Mapped(map));
+            }
+        }
+        MappedBst::open_in_memory(path)
+    }
+
+    /**
+     * Open a file written by write_file, reading it whole into memory
+     */
+    pub fn open_in_memory<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let mut bytes: Vec<u8> = Vec::new();
+        File::open(path)?.read_to_end(&mut bytes)?;
+        MappedBst::from_storage(Storage::InMemory(bytes))
+    }
+
+    fn from_storage(storage: Storage) -> io::Result<Self> {
+        let mut tree = MappedBst { storage, len: 0 };
+        let bytes = tree.bytes();
+        if bytes.len() < HEADER_LEN || &bytes[0..4] != MAGIC {
+            return Err(invalid_data("not an Eytzinger tree file".to_string()));
+        }
+        if bytes[4] != VERSION {
+            return Err(invalid_data(format!("unsupported format version {}", bytes[4])));
+        }
+        let count = u64::from_le_bytes(bytes[8..16].try_into().unwrap());
+        let expected = (count as usize).checked_mul(KEY_LEN).and_then(|keys| keys.checked_add(HEADER_LEN));
+        if expected != Some(bytes.len()) {
+            return Err(invalid_data(format!(
+                "file holds {} bytes, too short or too long for {} keys",
+                bytes.len(),
+                count
+            )));
+        }
+        tree.len = count as usize;
+        Ok(tree)
+    }
+
+    fn bytes(&self) -> &[u8] {
+        match &self.storage {
+            #[cfg(feature = "mmap")]
+            Storage::Mapped(map) => map,
+            Storage::InMemory(bytes) => bytes,
+        }
+    }
+
+    /**
+     * Return true if the file is memory-mapped rather than read into memory
+     */
+    pub fn is_mapped(&self) -> bool {
+        !matches!(self.storage, Storage::InMemory(_))
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    //key of the implicit node k, 1-based
+    fn key_at(&self, k: usize) -> i32 {
+        let offset = HEADER_LEN + (k - 1) * KEY_LEN;
+        i32::from_le_bytes(self.bytes()[offset..offset + KEY_LEN].try_into().unwrap())
+    }
+
+    pub fn search(&self, key: &i32) -> bool {
+        let mut k = 1;
+        while k <= self.len {
+            let current = self.key_at(k);
+            if current == *key {
+                return true;
+            }
+            k = 2 * k + usize::from(current < *key);
+        }
+        false
+    }
+
+    /**
+     * Largest key <= `key`
+     */
+    pub fn floor(&self, key: &i32) -> Option<i32> {
+        let mut k = 1;
+        while k <= self.len {
+            k = 2 * k + usize::from(self.key_at(k) <= *key);
+        }
+        //the floor is where the descent last went right: drop the trailing left turns and that right turn
+        k >>= k.trailing_zeros() + 1;
+        (k != 0).then(|| self.key_at(k))
+    }
+
+    /**
+     * Smallest key >= `key`
+     */
+    pub fn ceiling(&self, key: &i32) -> Option<i32> {
+        let mut k = 1;
+        while k <= self.len {
+            k = 2 * k + usize::from(self.key_at(k) < *key);
+        }
+        //the ceiling is where the descent last went left: drop the trailing right turns and that left turn
+        k >>= k.trailing_ones() + 1;
+        (k != 0).then(|| self.key_at(k))
+    }
+
+    /**
+     * Keys in ascending order
+     */
+    pub fn iter(&self) -> impl Iterator<Item = i32> + '_ {
+        inorder_indices(self.len).map(|k| self.key_at(k))
+    }
+}
@@ -1,11 +1,45 @@
+pub mod arena_bst;
+pub mod augmented_bst;
 pub mod bst;
+#[cfg(feature = "serde")]
+pub mod bst_serde;
+pub mod interval_bst;
+pub mod mapped_bst;
+pub mod persistent_bst;
+pub mod sync_bst;
+pub mod tree_codec;
+pub mod tree_compare;
+pub mod tree_convert;
+pub mod tree_notation;
+pub mod tree_paths;
+pub mod tree_rebuild;
+pub mod tree_shape;
+pub mod tree_traversal;
+pub mod tree_walk;
 pub mod tree {
     use std::cell::RefCell;
+    use std::collections::VecDeque;
+    use std::fmt;
     use std::rc::{Rc, Weak};
 
     pub type NodeLink = Rc<RefCell<Node>>;
     pub type WeakNodeLink = Weak<RefCell<Node>>;
 
+    //a value of a level order that no node can take as a child
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct LevelOrderError {
+        pub index: usize,
+        pub value: i32,
+    }
+
+    impl fmt::Display for LevelOrderError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "value {} at index {} has no parent left to hang from", self.value, self.index)
+        }
+    }
+
+    impl std::error::Error for LevelOrderError {}
+
     #[derive(Debug, Clone)]
     pub struct Node {
         pub value: i32,
@@ -14,6 +48,25 @@ pub mod tree {
         pub right: Option<NodeLink>,
     }
 
+    /**
+     * Drop children iteratively, the default recursive drop overflows the stack on degenerate trees
+     */
+    impl Drop for Node {
+        fn drop(&mut self) {
+            let mut stack: Vec<NodeLink> = Vec::new();
+            stack.extend(self.left.take());
+            stack.extend(self.right.take());
+            while let Some(node) = stack.pop() {
+                //only tear down nodes nobody else holds
+                if let Ok(cell) = Rc::try_unwrap(node) {
+                    let mut child = cell.into_inner();
+                    stack.extend(child.left.take());
+                    stack.extend(child.right.take());
+                }
+            }
+        }
+    }
+
     impl Node {
         //private interface
         fn new(value: i32) -> Self {
@@ -27,8 +80,7 @@ pub mod tree {
 
         pub fn new_nodelink(value: i32) -> NodeLink {
             let currentnode = Node::new(value);
-            let currentlink = Rc::new(RefCell::new(currentnode));
-            currentlink
+            Rc::new(RefCell::new(currentnode))
         }
 
         /**
@@ -41,22 +93,104 @@ pub mod tree {
 
         /**
          * Get a copy of node link
+         * Shallow: the children are still shared with self, use deep_clone for an independent tree
          */
         pub fn get_nodelink_copy(&self) -> NodeLink {
             Rc::new(RefCell::new(self.clone()))
         }
 
+        /**
+         * Copy the whole tree rooted at `root` into fresh nodes (Iterative version)
+         * Every parent link points into the copy, the copied root has no parent
+         */
+        pub fn deep_clone(root: &NodeLink) -> NodeLink {
+            let new_root = Node::new_nodelink(root.borrow().value);
+            //pairs of (original node, its copy) whose children are still to be copied
+            let mut stack: Vec<(NodeLink, NodeLink)> = vec![(Rc::clone(root), Rc::clone(&new_root))];
+
+            while let Some((original, copy)) = stack.pop() {
+                let original_ref = original.borrow();
+                if let Some(left) = &original_ref.left {
+                    copy.borrow_mut().add_left_child(&copy, left.borrow().value);
+                    let left_copy = copy.borrow().left.clone().unwrap();
+                    stack.push((Rc::clone(left), left_copy));
+                }
+                if let Some(right) = &original_ref.right {
+                    copy.borrow_mut().add_right_child(&copy, right.borrow().value);
+                    let right_copy = copy.borrow().right.clone().unwrap();
+                    stack.push((Rc::clone(right), right_copy));
+                }
+            }
+            new_root
+        }
+
+        /**
+         * Build a tree from its level order, LeetCode style: [5, 3, 7, 2, 4, None, 10]
+         * Children are only listed for nodes that exist, trailing Nones may be left out or padded
+         * Return None for an empty slice or a None root,
+         * and an error if a value is left over once every node got its children
+         */
+        pub fn from_level_order(values: &[Option<i32>]) -> Result<Option<NodeLink>, LevelOrderError> {
+            let root = values.first().copied().flatten().map(Node::new_nodelink);
+            //nodes waiting for their children, in level order
+            let mut queue: VecDeque<NodeLink> = root.iter().cloned().collect();
+            //index of the next child slot to read
+            let mut next = 1;
+
+            while next < values.len() {
+                let Some(node) = queue.pop_front() else {
+                    break;
+                };
+                if let Some(value) = values[next] {
+                    node.borrow_mut().add_left_child(&node, value);
+                    queue.push_back(node.borrow().left.clone().unwrap());
+                }
+                if let Some(Some(value)) = values.get(next + 1) {
+                    node.borrow_mut().add_right_child(&node, *value);
+                    queue.push_back(node.borrow().right.clone().unwrap());
+                }
+                next += 2;
+            }
+
+            let leftover = values.iter().enumerate().skip(next).find_map(|(index, value)| value.map(|v| (index, v)));
+            match leftover {
+                Some((index, value)) => Err(LevelOrderError { index, value }),
+                None => Ok(root),
+            }
+        }
+
+        /**
+         * The inverse of from_level_order, trailing Nones are trimmed
+         */
+        pub fn to_level_order(root: &NodeLink) -> Vec<Option<i32>> {
+            let mut values: Vec<Option<i32>> = Vec::new();
+            let mut queue: VecDeque<Option<NodeLink>> = VecDeque::from([Some(Rc::clone(root))]);
+            while let Some(slot) = queue.pop_front() {
+                match slot {
+                    None => values.push(None),
+                    Some(node) => {
+                        let node_ref = node.borrow();
+                        values.push(Some(node_ref.value));
+                        queue.push_back(node_ref.left.clone());
+                        queue.push_back(node_ref.right.clone());
+                    }
+                }
+            }
+            while values.last() == Some(&None) {
+                values.pop();
+            }
+            values
+        }
+
         //private interface
         fn new_with_parent(parent: &NodeLink, value: i32) -> NodeLink {
             let mut currentnode = Node::new(value);
             currentnode.add_parent(Rc::<RefCell<Node>>::downgrade(parent));
-            let currentlink = Rc::new(RefCell::new(currentnode));
-            currentlink
+            Rc::new(RefCell::new(currentnode))
         }
 
         fn new_from_node(node: Node) -> NodeLink {
-            let currentlink = Rc::new(RefCell::new(node));
-            currentlink
+            Rc::new(RefCell::new(node))
         }
 
         //add new left child, set the parent to current_node_link
@@ -80,10 +214,7 @@ pub mod tree {
          * As the name implied, used to upgrade parent node to strong nodelink
          */
         pub fn upgrade_weak_to_strong(node: Option<WeakNodeLink>) -> Option<NodeLink> {
-            match node {
-                None => None,
-                Some(x) => Some(x.upgrade().unwrap()),
-            }
+            node.map(|x| x.upgrade().unwrap())
         }
 
         /**
@@ -91,14 +222,7 @@ pub mod tree {
          */
         #[allow(dead_code)]
         fn is_node_match_weak_strong(node1: Option<WeakNodeLink>, node2: Option<NodeLink>) -> bool {
-            let node1s: Option<Rc<RefCell<Node>>> = Node::upgrade_weak_to_strong(node1);
-            if node1s.is_none() && node2.is_none() {
-                return true;
-            }
-            if let Some(node1sv) = node1s {
-                return node2.is_some_and(|x: NodeLink| x.borrow().value == node1sv.borrow().value);
-            }
-            false
+            Node::is_node_match_both_strong(Node::upgrade_weak_to_strong(node1), node2)
         }
 
         //helper function
@@ -108,110 +232,132 @@ pub mod tree {
         ) -> bool {
             let node1s: Option<Rc<RefCell<Node>>> = Node::upgrade_weak_to_strong(node1);
             let node2s: Option<Rc<RefCell<Node>>> = Node::upgrade_weak_to_strong(node2);
-            if node1s.is_none() && node2s.is_none() {
-                return true;
-            }
-            return Node::is_node_match_both_strong(node1s, node2s);
+            Node::is_node_match_both_strong(node1s, node2s)
         }
 
-        //helper function to compare both nodelink
+        /**
+         * helper function to compare both nodelink
+         * The whole subtrees are compared (values and shape), not just the values on top (Iterative version)
+         */
         fn is_node_match_both_strong(node1: Option<NodeLink>, node2: Option<NodeLink>) -> bool {
-            if node1.is_none() && node2.is_none() {
-                return true;
-            }
-            if let Some(node1v) = node1 {
-                return node2.is_some_and(|x: NodeLink| x.borrow().value == node1v.borrow().value);
+            let mut stack: Vec<(Option<NodeLink>, Option<NodeLink>)> = vec![(node1, node2)];
+            while let Some(pair) = stack.pop() {
+                match pair {
+                    (None, None) => {}
+                    (Some(a), Some(b)) => {
+                        if Rc::ptr_eq(&a, &b) {
+                            continue;
+                        }
+                        let (a_ref, b_ref) = (a.borrow(), b.borrow());
+                        if a_ref.value != b_ref.value {
+                            return false;
+                        }
+                        stack.push((a_ref.left.clone(), b_ref.left.clone()));
+                        stack.push((a_ref.right.clone(), b_ref.right.clone()));
+                    }
+                    _ => return false,
+                }
             }
-            return false;
+            true
         }
 
         /**
-         * This function will return the node that match value
-         * Let's assume the tree won't have any value duplicates
+         * This function will return the node that match value, as the real handle inside the tree
+         * Both subtrees are searched (pre-order, left first), the first match is returned
          */
-        pub fn get_node_by_value(&self, value: i32) -> Option<NodeLink>{
-            //check current node value
-            if self.value == value {
-                //create clone of NodeLink
-                let node = self.clone();
-                let nodelink = Rc::new(RefCell::new(node));
-                return Rc::<RefCell<Node>>::downgrade(&nodelink).upgrade();
-            }
-            //go left if exist
-            if let Some(x) = &self.left {
-                return x.borrow().get_node_by_value(value);
+        pub fn get_node_by_value(node: &NodeLink, value: i32) -> Option<NodeLink> {
+            let mut stack: Vec<NodeLink> = vec![Rc::clone(node)];
+            while let Some(current) = stack.pop() {
+                if current.borrow().value == value {
+                    return Some(current);
+                }
+                //push right first so the left subtree is searched first
+                stack.extend(current.borrow().right.clone());
+                stack.extend(current.borrow().left.clone());
             }
-            if let Some(x) = &self.right {
-                return x.borrow().get_node_by_value(value);
+            None
+        }
+
+        /**
+         * Same as get_node_by_value but return every node that match value, in pre-order
+         * for trees that hold duplicate values
+         */
+        pub fn find_all_by_value(node: &NodeLink, value: i32) -> Vec<NodeLink> {
+            let mut found: Vec<NodeLink> = Vec::new();
+            let mut stack: Vec<NodeLink> = vec![Rc::clone(node)];
+            while let Some(current) = stack.pop() {
+                stack.extend(current.borrow().right.clone());
+                stack.extend(current.borrow().left.clone());
+                if current.borrow().value == value {
+                    found.push(current);
+                }
             }
-            return None;
+            found
         }
 
         /**
          * This function will return the node that matches all Nodelink Properties:
          * 1). current node value,
-         * 2). node parent value,
-         * 3). both child values
+         * 2). node parent, compared with its whole subtree,
+         * 3). both child subtrees, values and shape
          * Let's assume the tree won't have any value duplicates
-         * Asssume 2nd parameter is not None
+         * The whole tree rooted at `root` is searched, the real handle inside the tree is returned
          */
-        pub fn get_node_by_full_property(&self, node: &NodeLink) -> Option<NodeLink> {
-            //check current node value
-            let nodevalue = node.borrow().value;
-            let check_parent_eq = Node::is_node_match_both_weak(
-                node.borrow().parent.clone(),
-                self.parent.clone(),
-            );
-            let check_left_child_eq = Node::is_node_match_both_strong(
-                node.borrow().left.clone(),
-                self.left.clone(),
-            );
-            let check_right_child_eq = Node::is_node_match_both_strong(
-                node.borrow().right.clone(),
-                self.right.clone(),
-            );
-            if self.value == nodevalue
-                && check_parent_eq
-                && check_left_child_eq
-                && check_right_child_eq
-            {
-                return Some(self.get_nodelink_copy());
-            } else{
-                //recurse deeper if not found
-                //recurse to left
-                if let Some(left_subtree) = &self.left{
-                    return left_subtree.borrow().get_node_by_full_property(node);
-                } else if let Some(right_subtree) = &self.right{
-                    //recurse to right
-                    return right_subtree.borrow().get_node_by_full_property(node);
+        pub fn get_node_by_full_property(root: &NodeLink, node: &NodeLink) -> Option<NodeLink> {
+            let node_ref = node.borrow();
+            let mut stack: Vec<NodeLink> = vec![Rc::clone(root)];
+            while let Some(current) = stack.pop() {
+                let current_ref = current.borrow();
+                if current_ref.value == node_ref.value
+                    && Node::is_node_match_both_weak(current_ref.parent.clone(), node_ref.parent.clone())
+                    && Node::is_node_match_both_strong(current_ref.left.clone(), node_ref.left.clone())
+                    && Node::is_node_match_both_strong(current_ref.right.clone(), node_ref.right.clone())
+                {
+                    drop(current_ref);
+                    return Some(current);
                 }
+                stack.extend(current_ref.right.clone());
+                stack.extend(current_ref.left.clone());
             }
             None
         }
 
         /**
-         * This function will discard a node that match the value, the whole node tree that match the description will be discarded
-         * Along with its child
-         * The concept how we discard the node is, if the current node match sever the connection with parent,
-         * Then after return from the completion of immediate recursive, sever the connection with the child
+         * This function will discard the first node (pre-order) that match the value, along with its child
+         * The matched subtree is cut from its parent and returned as its own root, the rest of the tree is left intact
+         * If the root itself match, there is nothing to cut from and the whole tree is returned
+         * Return None when no node match
          */
-        pub fn discard_node_by_value(&mut self, value: i32) -> bool {
-            //check current node value
-            if  self.value == value{
-                //cut off parent connection
-                self.parent = None;
-                return true;
-            } else if let Some(left_node) = &self.left {
-                let result_flag = left_node.borrow_mut().discard_node_by_value(value);
-                //cut this child connection
-                self.left = None;
-                return result_flag;
-            } else if let Some(right_node) = &self.right {
-                let result_flag = right_node.borrow_mut().discard_node_by_value(value);
-                self.right = None;
-                return result_flag;
+        pub fn discard_node_by_value(root: &NodeLink, value: i32) -> Option<NodeLink> {
+            //the parent is tracked by the walk itself, so a shallow copy of the root is handled too
+            let mut stack: Vec<(NodeLink, Option<NodeLink>)> = vec![(Rc::clone(root), None)];
+            while let Some((current, parent)) = stack.pop() {
+                if current.borrow().value == value {
+                    if let Some(parent_node) = parent {
+                        let mut parent_mut = parent_node.borrow_mut();
+                        if parent_mut.left.as_ref().is_some_and(|left| Rc::ptr_eq(left, &current)) {
+                            parent_mut.left = None;
+                        } else {
+                            parent_mut.right = None;
+                        }
+                        drop(parent_mut);
+                        //a node shared with another tree keeps its parent there
+                        let mut current_mut = current.borrow_mut();
+                        if current_mut.parent.as_ref().is_some_and(|weak| weak.ptr_eq(&Rc::downgrade(&parent_node))) {
+                            current_mut.parent = None;
+                        }
+                    }
+                    return Some(current);
+                }
+                let current_ref = current.borrow();
+                if let Some(right) = &current_ref.right {
+                    stack.push((Rc::clone(right), Some(Rc::clone(&current))));
+                }
+                if let Some(left) = &current_ref.left {
+                    stack.push((Rc::clone(left), Some(Rc::clone(&current))));
+                }
             }
-            false
+            None
         }
 
         /**
@@ -222,7 +368,7 @@ pub mod tree {
             let mut count = 0;
             let nodelink: Rc<RefCell<Node>> = Node::new_from_node(self.clone());
             count = Node::count_nodes_by_nodelink(&nodelink, count);
-            return count;
+            count
         }
 
         //the same as above except start the count from nodelink reference parameter
@@ -230,12 +376,12 @@ pub mod tree {
             let mut left_count: i32 = 0;
             let mut right_count: i32 = 0;
             if let Some(left_child) = &node.borrow().left {
-                left_count = Node::count_nodes_by_nodelink(&left_child, count);
+                left_count = Node::count_nodes_by_nodelink(left_child, count);
             }
             if let Some(right_child) = &node.borrow().right {
-                right_count = Node::count_nodes_by_nodelink(&right_child, count);
+                right_count = Node::count_nodes_by_nodelink(right_child, count);
             }
-            return count + left_count + right_count + 1;
+            count + left_count + right_count + 1
         }
 
         /**Count depth of the tree in the current node
@@ -244,10 +390,11 @@ pub mod tree {
         pub fn tree_depth(&self) -> i32 {
             let depth: i32 = 0;
             let nodelink: Rc<RefCell<Node>> = Node::new_from_node(self.clone());
-            return self.track_depth(&nodelink, depth);
+            self.track_depth(&nodelink, depth)
         }
 
         //track depth by traversing all nodes but returned depth count per path. The highest number will be returned
+        #[allow(clippy::only_used_in_recursion)]
         fn track_depth(&self, node: &NodeLink, depth: i32) -> i32 {
             let mut left_depth: i32 = 0;
             let mut right_depth: i32 = 0;
@@ -266,6 +413,28 @@ pub mod tree {
             right_depth
         }
 
+        /**
+         * Check the whole subtree against the BST ordering (Iterative version)
+         * Every node is compared with the bounds set by all its ancestors, not just its parent,
+         * values must be strictly increasing in-order, so duplicates are not a BST
+         */
+        pub fn is_bst(&self) -> bool {
+            //(node, exclusive lower bound, exclusive upper bound)
+            let mut stack: Vec<(NodeLink, Option<i32>, Option<i32>)> = Vec::new();
+            stack.extend(self.left.clone().map(|left| (left, None, Some(self.value))));
+            stack.extend(self.right.clone().map(|right| (right, Some(self.value), None)));
+            while let Some((node, low, high)) = stack.pop() {
+                let node_ref = node.borrow();
+                let value = node_ref.value;
+                if low.is_some_and(|l| value <= l) || high.is_some_and(|h| value >= h) {
+                    return false;
+                }
+                stack.extend(node_ref.left.clone().map(|left| (left, low, Some(value))));
+                stack.extend(node_ref.right.clone().map(|right| (right, Some(value), high)));
+            }
+            true
+        }
+
         /**
          * a node is guaranteed to have two childs at most, since this is a binary tree
          * a sibling is a node which has same direct parent
@@ -0,0 +1,222 @@
+//this package implement a persistent BST, every version stays readable after insert/remove
+//insert and remove copy only the nodes on the search path, the rest is shared with the older version
+//nodes are immutable and have no parent link, otherwise a shared subtree would need several parents
+
+use std::cmp::Ordering;
+use std::hash::{Hash, Hasher};
+use std::rc::Rc;
+
+type PersistentNodeLink = Rc<PersistentNode>;
+
+#[derive(Debug)]
+struct PersistentNode {
+    key: i32,
+    left: Option<PersistentNodeLink>,
+    right: Option<PersistentNodeLink>,
+}
+
+/**
+ * Drop children iteratively, a deep unshared path would otherwise overflow the stack
+ */
+impl Drop for PersistentNode {
+    fn drop(&mut self) {
+        let mut stack: Vec<PersistentNodeLink> = Vec::new();
+        stack.extend(self.left.take());
+        stack.extend(self.right.take());
+        while let Some(node) = stack.pop() {
+            //nodes still used by another version are left alone
+            if let Ok(mut child) = Rc::try_unwrap(node) {
+                stack.extend(child.left.take());
+                stack.extend(child.right.take());
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct PersistentBst {
+    root: Option<PersistentNodeLink>,
+    len: usize,
+}
+
+//a node on the search path and whether the search went to its left child
+type PathStep = (PersistentNodeLink, bool);
+
+impl PersistentBst {
+    pub fn new() -> Self {
+        PersistentBst::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /**
+     * O(1), the snapshot shares every node with the current version
+     */
+    pub fn snapshot(&self) -> Self {
+        self.clone()
+    }
+
+    /**
+     * Return true if both versions share the same root, i.e. nothing changed in between
+     */
+    pub fn ptr_eq(&self, other: &PersistentBst) -> bool {
+        match (&self.root, &other.root) {
+            (None, None) => true,
+            (Some(a), Some(b)) => Rc::ptr_eq(a, b),
+            _ => false,
+        }
+    }
+
+    //search the tree which node fit the key (Iterative version)
+    pub fn contains(&self, key: &i32) -> bool {
+        let mut current = self.root.as_ref();
+        while let Some(node) = current {
+            if *key == node.key {
+                return true;
+            }
+            current = if *key < node.key { node.left.as_ref() } else { node.right.as_ref() };
+        }
+        false
+    }
+
+    /**
+     * Copy the nodes of `path` bottom-up, hanging `child` where the search left off
+     * Returns the new root
+     */
+    fn rebuild(path: Vec<PathStep>, mut child: Option<PersistentNodeLink>) -> Option<PersistentNodeLink> {
+        for (node, went_left) in path.into_iter().rev() {
+            let (left, right) = if went_left {
+                (child, node.right.clone())
+            } else {
+                (node.left.clone(), child)
+            };
+            child = Some(Rc::new(PersistentNode { key: node.key, left, right }));
+        }
+        child
+    }
+
+    /**
+     * Return a new version holding `key` as well, this version is left untouched
+     * If the key already exists, the returned version is this one
+     */
+    pub fn insert(&self, key: i32) -> PersistentBst {
+        let mut path: Vec<PathStep> = Vec::new();
+        let mut current = self.root.clone();
+        while let Some(node) = current {
+            if key == node.key {
+                return self.snapshot();
+            }
+            let went_left = key < node.key;
+            current = if went_left { node.left.clone() } else { node.right.clone() };
+            path.push((node, went_left));
+        }
+
+        let leaf = Rc::new(PersistentNode { key, left: None, right: None });
+        PersistentBst {
+            root: PersistentBst::rebuild(path, Some(leaf)),
+            len: self.len + 1,
+        }
+    }
+
+    /**
+     * Return a new version without `key`, this version is left untouched
+     * If the key doesn't exist, the returned version is this one
+     */
+    pub fn remove(&self, key: &i32) -> PersistentBst {
+        let mut path: Vec<PathStep> = Vec::new();
+        let mut current = self.root.clone();
+        let z = loop {
+            let Some(node) = current else {
+                return self.snapshot();
+            };
+            if *key == node.key {
+                break node;
+            }
+            let went_left = *key < node.key;
+            current = if went_left { node.left.clone() } else { node.right.clone() };
+            path.push((node, went_left));
+        };
+
+        let replacement = match (&z.left, &z.right) {
+            (None, _) => z.right.clone(),
+            (_, None) => z.left.clone(),
+            (Some(_), Some(z_right)) => {
+                // take the successor out of the right subtree, copying the left spine down to it
+                let mut spine: Vec<PathStep> = Vec::new();
+                let mut successor = Rc::clone(z_right);
+                while let Some(left) = successor.left.clone() {
+                    spine.push((successor, true));
+                    successor = left;
+                }
+                let new_right = PersistentBst::rebuild(spine, successor.right.clone());
+                Some(Rc::new(PersistentNode {
+                    key: successor.key,
+                    left: z.left.clone(),
+                    right: new_right,
+                }))
+            }
+        };
+
+        PersistentBst {
+            root: PersistentBst::rebuild(path, replacement),
+            len: self.len - 1,
+        }
+    }
+
+    /**
+     * All keys of this version in ascending order (Iterative version)
+     */
+    pub fn inorder_keys(&self) -> Vec<i32> {
+        let mut keys: Vec<i32> = Vec::with_capacity(self.len);
+        let mut stack: Vec<&PersistentNodeLink> = Vec::new();
+        let mut current = self.root.as_ref();
+        while current.is_some() || !stack.is_empty() {
+            while let Some(node) = current {
+                stack.push(node);
+                current = node.left.as_ref();
+            }
+            let node = stack.pop().unwrap();
+            keys.push(node.key);
+            current = node.right.as_ref();
+        }
+        keys
+    }
+}
+
+/**
+ * Two versions are equal when they hold the same keys, whatever their shape
+ * Only versions are compared this way, BstNodeLink trees have no such equality:
+ * Rc<RefCell<BstNode>> is not a type of this crate, compare their inorder keys to get the same result
+ */
+impl PartialEq for PersistentBst {
+    fn eq(&self, other: &Self) -> bool {
+        self.ptr_eq(other) || (self.len == other.len && self.inorder_keys() == other.inorder_keys())
+    }
+}
+
+impl Eq for PersistentBst {}
+
+impl Hash for PersistentBst {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.inorder_keys().hash(state);
+    }
+}
+
+//lexicographic order of the sorted keys
+impl PartialOrd for PersistentBst {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for PersistentBst {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.inorder_keys().cmp(&other.inorder_keys())
+    }
+}
@@ -0,0 +1,81 @@
+//this package implement a Send + Sync BST, the arena tree behind one RwLock shared through an Arc
+//node indexes can go stale as soon as the lock is released, so the API here only talks in keys
+
+use crate::structure::arena_bst::ArenaBst;
+use std::sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+/**
+ * Cloning a SyncBst is cheap and gives another handle to the same tree
+ * Unlike ArenaBst it has no key based PartialEq, Hash or Ord, compare inorder_keys snapshots instead
+ */
+#[derive(Debug, Clone, Default)]
+pub struct SyncBst {
+    inner: Arc<RwLock<ArenaBst>>,
+}
+
+impl SyncBst {
+    pub fn new() -> Self {
+        SyncBst::default()
+    }
+
+    //private interface, a panic while holding the lock may have left the tree half linked
+    fn read(&self) -> RwLockReadGuard<'_, ArenaBst> {
+        self.inner.read().expect("tree lock poisoned by a panicked writer")
+    }
+
+    fn write(&self) -> RwLockWriteGuard<'_, ArenaBst> {
+        self.inner.write().expect("tree lock poisoned by a panicked writer")
+    }
+
+    pub fn len(&self) -> usize {
+        self.read().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.read().is_empty()
+    }
+
+    /**
+     * Insert `key`, return false if the key already exists
+     */
+    pub fn tree_insert(&self, key: i32) -> bool {
+        self.write().tree_insert(key).is_some()
+    }
+
+    pub fn contains(&self, key: &i32) -> bool {
+        self.read().tree_search(key).is_some()
+    }
+
+    /**
+     * Delete the node holding `key`, returning the removed key
+     */
+    pub fn remove(&self, key: &i32) -> Option<i32> {
+        self.write().remove(key)
+    }
+
+    pub fn minimum(&self) -> Option<i32> {
+        let tree = self.read();
+        tree.root().and_then(|root| tree.key(tree.minimum(root)))
+    }
+
+    pub fn maximum(&self) -> Option<i32> {
+        let tree = self.read();
+        tree.root().and_then(|root| tree.key(tree.maximum(root)))
+    }
+
+    /**
+     * Smallest key greater than `key`, `key` itself must be in the tree
+     */
+    pub fn tree_successor(&self, key: &i32) -> Option<i32> {
+        let tree = self.read();
+        let index = tree.tree_search(key)?;
+        tree.tree_successor(index).and_then(|successor| tree.key(successor))
+    }
+
+    /**
+     * All keys in ascending order, read under a single lock so the result is a consistent view
+     */
+    pub fn inorder_keys(&self) -> Vec<i32> {
+        self.read().inorder_keys()
+    }
+}
@@ -0,0 +1,329 @@
+//this package implement a compact binary file format for BstNode and tree::Node trees
+//
+//layout, all integers little endian:
+//- magic "BTRE" (4 bytes)
+//- format version (1 byte), currently 1
+//- tree kind (1 byte): 0 for tree::Node, 1 for BstNode
+//- node count (unsigned LEB128 varint)
+//- shape: 2 bits per node in pre-order (has left child, has right child), packed from the low bit, padded to a byte
+//- keys: one zigzag LEB128 varint per node in pre-order
+//- CRC32 (IEEE) of every byte before it (4 bytes)
+
+use crate::structure::bst::{BstNode, BstNodeLink, KeyBounds};
+use crate::structure::tree::{Node, NodeLink};
+use std::fmt;
+use std::io::{self, Read, Write};
+use std::rc::Rc;
+
+const MAGIC: &[u8; 4] = b"BTRE";
+const VERSION: u8 = 1;
+const KIND_NODE: u8 = 0;
+const KIND_BST: u8 = 1;
+
+#[derive(Debug)]
+pub enum CodecError {
+    Io(io::Error),
+    //the input ended before the structure was complete
+    Truncated,
+    BadMagic([u8; 4]),
+    UnsupportedVersion(u8),
+    WrongKind { expected: u8, found: u8 },
+    ChecksumMismatch { stored: u32, computed: u32 },
+    //checksum is fine but the content doesn't describe a valid tree
+    Malformed(String),
+}
+
+impl fmt::Display for CodecError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CodecError::Io(error) => write!(f, "i/o error: {}", error),
+            CodecError::Truncated => write!(f, "input is truncated"),
+            CodecError::BadMagic(found) => write!(f, "not a tree file, magic is {:?}", found),
+            CodecError::UnsupportedVersion(version) => write!(f, "unsupported format version {}", version),
+            CodecError::WrongKind { expected, found } => {
+                write!(f, "expected tree kind {} but the file holds kind {}", expected, found)
+            }
+            CodecError::ChecksumMismatch { stored, computed } => write!(
+                f,
+                "checksum mismatch, stored {:08x} but content gives {:08x}",
+                stored, computed
+            ),
+            CodecError::Malformed(reason) => write!(f, "malformed tree: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for CodecError {}
+
+impl From<io::Error> for CodecError {
+    fn from(error: io::Error) -> Self {
+        if error.kind() == io::ErrorKind::UnexpectedEof {
+            CodecError::Truncated
+        } else {
+            CodecError::Io(error)
+        }
+    }
+}
+
+const CRC_TABLE: [u32; 256] = crc_table();
+
+const fn crc_table() -> [u32; 256] {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 == 1 { (crc >> 1) ^ 0xEDB8_8320 } else { crc >> 1 };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+}
+
+fn crc32_update(crc: u32, bytes: &[u8]) -> u32 {
+    let mut crc = !crc;
+    for &byte in bytes {
+        crc = CRC_TABLE[((crc ^ byte as u32) & 0xFF) as usize] ^ (crc >> 8);
+    }
+    !crc
+}
+
+//reader keeping the checksum of every byte taken out of it
+struct CrcReader<'a, R: Read> {
+    inner: &'a mut R,
+    crc: u32,
+}
+
+impl<R: Read> CrcReader<'_, R> {
+    fn read_bytes(&mut self, buf: &mut [u8]) -> Result<(), CodecError> {
+        self.inner.read_exact(buf)?;
+        self.crc = crc32_update(self.crc, buf);
+        Ok(())
+    }
+
+    //a corrupted length must not allocate up front, the buffer only grows with the bytes really read
+    fn read_vec(&mut self, len: usize) -> Result<Vec<u8>, CodecError> {
+        let mut buf: Vec<u8> = Vec::new();
+        (&mut *self.inner).take(len as u64).read_to_end(&mut buf)?;
+        if buf.len() != len {
+            return Err(CodecError::Truncated);
+        }
+        self.crc = crc32_update(self.crc, &buf);
+        Ok(buf)
+    }
+
+    fn read_u8(&mut self) -> Result<u8, CodecError> {
+        let mut buf = [0u8; 1];
+        self.read_bytes(&mut buf)?;
+        Ok(buf[0])
+    }
+
+    fn read_varint(&mut self) -> Result<u64, CodecError> {
+        let mut value: u64 = 0;
+        for shift in (0..64).step_by(7) {
+            let byte = self.read_u8()?;
+            value |= ((byte & 0x7F) as u64) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(CodecError::Malformed("varint longer than 64 bits".to_string()))
+    }
+}
+
+fn push_varint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push((value as u8 & 0x7F) | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+fn zigzag(value: i32) -> u64 {
+    ((value << 1) ^ (value >> 31)) as u32 as u64
+}
+
+fn unzigzag(value: u64) -> Result<i32, CodecError> {
+    let value = u32::try_from(value).map_err(|_| CodecError::Malformed("key out of i32 range".to_string()))?;
+    Ok(((value >> 1) as i32) ^ -((value & 1) as i32))
+}
+
+//one node in pre-order: its key and whether it has a left and a right child
+type PreorderEntry = (i32, bool, bool);
+
+fn encode<W: Write>(writer: &mut W, kind: u8, nodes: &[PreorderEntry]) -> io::Result<()> {
+    let mut out: Vec<u8> = Vec::new();
+    out.extend_from_slice(MAGIC);
+    out.push(VERSION);
+    out.push(kind);
+    push_varint(&mut out, nodes.len() as u64);
+
+    let mut shape = vec![0u8; (nodes.len() * 2).div_ceil(8)];
+    for (index, &(_, has_left, has_right)) in nodes.iter().enumerate() {
+        let bit = index * 2;
+        shape[bit / 8] |= (has_left as u8) << (bit % 8);
+        shape[bit / 8] |= (has_right as u8) << (bit % 8 + 1);
+    }
+    out.extend_from_slice(&shape);
+    for &(key, _, _) in nodes {
+        push_varint(&mut out, zigzag(key));
+    }
+
+    let crc = crc32_update(0, &out);
+    out.extend_from_slice(&crc.to_le_bytes());
+    writer.write_all(&out)
+}
+
+fn decode<R: Read>(reader: &mut R, kind: u8) -> Result<Vec<PreorderEntry>, CodecError> {
+    let mut input = CrcReader { inner: reader, crc: 0 };
+    let mut magic = [0u8; 4];
+    input.read_bytes(&mut magic)?;
+    if &magic != MAGIC {
+        return Err(CodecError::BadMagic(magic));
+    }
+    let version = input.read_u8()?;
+    if version != VERSION {
+        return Err(CodecError::UnsupportedVersion(version));
+    }
+    let found_kind = input.read_u8()?;
+    if found_kind != kind {
+        return Err(CodecError::WrongKind { expected: kind, found: found_kind });
+    }
+
+    let count = input.read_varint()?;
+    //a count whose shape can't even be sized is a corrupt header, not a short input
+    let count_too_large = || CodecError::Malformed("node count too large".to_string());
+    let count = usize::try_from(count).map_err(|_| count_too_large())?;
+    let shape_len = count.checked_mul(2).map(|bits| bits.div_ceil(8)).ok_or_else(count_too_large)?;
+    let shape = input.read_vec(shape_len)?;
+    let mut nodes: Vec<PreorderEntry> = Vec::new();
+    for index in 0..count {
+        let bit = index * 2;
+        let has_left = shape[bit / 8] >> (bit % 8) & 1 == 1;
+        let has_right = shape[bit / 8] >> (bit % 8 + 1) & 1 == 1;
+        let key = unzigzag(input.read_varint()?)?;
+        nodes.push((key, has_left, has_right));
+    }
+
+    let computed = input.crc;
+    let mut stored = [0u8; 4];
+    input.inner.read_exact(&mut stored)?;
+    let stored = u32::from_le_bytes(stored);
+    if stored != computed {
+        return Err(CodecError::ChecksumMismatch { stored, computed });
+    }
+    Ok(nodes)
+}
+
+/**
+ * Check that the pre-order flags describe exactly one tree
+ * Returns, for every node after the root, the index of its parent and whether it is a left child
+ */
+fn link_preorder(nodes: &[PreorderEntry]) -> Result<Vec<(usize, bool)>, CodecError> {
+    let mut links: Vec<(usize, bool)> = Vec::with_capacity(nodes.len().saturating_sub(1));
+    //children slots still to be filled, the left slot of a node is always filled first
+    let mut slots: Vec<(usize, bool)> = Vec::new();
+    for (index, &(_, has_left, has_right)) in nodes.iter().enumerate() {
+        if index > 0 {
+            let slot = slots.pop().ok_or_else(|| {
+                CodecError::Malformed(format!("node {} is not reachable from the root", index))
+            })?;
+            links.push(slot);
+        }
+        if has_right {
+            slots.push((index, false));
+        }
+        if has_left {
+            slots.push((index, true));
+        }
+    }
+    if !slots.is_empty() {
+        return Err(CodecError::Malformed(format!("{} child(ren) announced but missing", slots.len())));
+    }
+    Ok(links)
+}
+
+impl BstNode {
+    /**
+     * Write the tree rooted at `root` in the binary tree format, None writes an empty tree
+     */
+    pub fn write_to<W: Write>(root: Option<&BstNodeLink>, writer: &mut W) -> io::Result<()> {
+        let mut nodes: Vec<PreorderEntry> = Vec::new();
+        let mut stack: Vec<BstNodeLink> = root.into_iter().cloned().collect();
+        while let Some(node) = stack.pop() {
+            let node_ref = node.borrow();
+            nodes.push((node_ref.key.unwrap(), node_ref.left.is_some(), node_ref.right.is_some()));
+            stack.extend(node_ref.right.clone());
+            stack.extend(node_ref.left.clone());
+        }
+        encode(writer, KIND_BST, &nodes)
+    }
+
+    /**
+     * Read a tree written by write_to, rebuilding the parent links
+     * Corrupted, truncated or out of order content is rejected
+     */
+    pub fn read_from<R: Read>(reader: &mut R) -> Result<Option<BstNodeLink>, CodecError> {
+        let nodes = decode(reader, KIND_BST)?;
+        let links = link_preorder(&nodes)?;
+        let Some(&(root_key, _, _)) = nodes.first() else {
+            return Ok(None);
+        };
+
+        let mut built: Vec<BstNodeLink> = vec![BstNode::new_bst_nodelink(root_key)];
+        //exclusive key bounds of every built node
+        let mut bounds: Vec<KeyBounds> = vec![(None, None)];
+        for (&(key, _, _), &(parent_index, is_left)) in nodes.iter().skip(1).zip(links.iter()) {
+            let (child, child_bounds) = BstNode::attach_checked(&built[parent_index], bounds[parent_index], is_left, key)
+                .map_err(CodecError::Malformed)?;
+            built.push(child);
+            bounds.push(child_bounds);
+        }
+        Ok(Some(Rc::clone(&built[0])))
+    }
+}
+
+impl Node {
+    /**
+     * Write the tree rooted at `root` in the binary tree format, None writes an empty tree
+     */
+    pub fn write_to<W: Write>(root: Option<&NodeLink>, writer: &mut W) -> io::Result<()> {
+        let mut nodes: Vec<PreorderEntry> = Vec::new();
+        let mut stack: Vec<NodeLink> = root.into_iter().cloned().collect();
+        while let Some(node) = stack.pop() {
+            let node_ref = node.borrow();
+            nodes.push((node_ref.value, node_ref.left.is_some(), node_ref.right.is_some()));
+            stack.extend(node_ref.right.clone());
+            stack.extend(node_ref.left.clone());
+        }
+        encode(writer, KIND_NODE, &nodes)
+    }
+
+    /**
+     * Read a tree written by write_to, rebuilding the parent links
+     * Corrupted or truncated content is rejected
+     */
+    pub fn read_from<R: Read>(reader: &mut R) -> Result<Option<NodeLink>, CodecError> {
+        let nodes = decode(reader, KIND_NODE)?;
+        let links = link_preorder(&nodes)?;
+        let Some(&(root_value, _, _)) = nodes.first() else {
+            return Ok(None);
+        };
+
+        let mut built: Vec<NodeLink> = vec![Node::new_nodelink(root_value)];
+        for (&(value, _, _), &(parent_index, is_left)) in nodes.iter().skip(1).zip(links.iter()) {
+            let parent = Rc::clone(&built[parent_index]);
+            if is_left {
+                parent.borrow_mut().add_left_child(&parent, value);
+                built.push(parent.borrow().left.clone().unwrap());
+            } else {
+                parent.borrow_mut().add_right_child(&parent, value);
+                built.push(parent.borrow().right.clone().unwrap());
+            }
+        }
+        Ok(Some(Rc::clone(&built[0])))
+    }
+}
@@ -0,0 +1,96 @@
+//this package compare whole tree::Node trees with each other
+//- contains_subtree: pre-order with null markers of both trees, then a KMP search of one in the other, O(n + m)
+//- is_isomorphic: every subtree gets a canonical id that ignores the order of its two children, O(n + m) expected
+//both walks are iterative
+
+use crate::structure::tree::{Node, NodeLink};
+use crate::structure::tree_walk::fold_up;
+use std::collections::HashMap;
+
+/**
+ * Pre-order of the subtree rooted at `node`, with None for every missing child
+ * Two subtrees are equal exactly when their sequences are, and a subtree shows up as a contiguous run
+ */
+fn preorder_tokens(node: &Node) -> Vec<Option<i32>> {
+    let mut tokens: Vec<Option<i32>> = vec![Some(node.value)];
+    let mut stack: Vec<Option<NodeLink>> = vec![node.right.clone(), node.left.clone()];
+    while let Some(slot) = stack.pop() {
+        match slot {
+            None => tokens.push(None),
+            Some(child) => {
+                let child_ref = child.borrow();
+                tokens.push(Some(child_ref.value));
+                stack.push(child_ref.right.clone());
+                stack.push(child_ref.left.clone());
+            }
+        }
+    }
+    tokens
+}
+
+//Knuth-Morris-Pratt search of `pattern` in `text`
+fn contains_run(text: &[Option<i32>], pattern: &[Option<i32>]) -> bool {
+    //failure[i]: length of the longest proper prefix of pattern[..=i] that is also its suffix
+    let mut failure = vec![0usize; pattern.len()];
+    let mut matched = 0;
+    for i in 1..pattern.len() {
+        while matched > 0 && pattern[i] != pattern[matched] {
+            matched = failure[matched - 1];
+        }
+        if pattern[i] == pattern[matched] {
+            matched += 1;
+        }
+        failure[i] = matched;
+    }
+
+    matched = 0;
+    for token in text {
+        while matched > 0 && *token != pattern[matched] {
+            matched = failure[matched - 1];
+        }
+        if *token == pattern[matched] {
+            matched += 1;
+            if matched == pattern.len() {
+                return true;
+            }
+        }
+    }
+    false
+}
+
+//id of a node from its value and its children ids, the same for either order of the children
+fn intern(ids: &mut HashMap<(i32, usize, usize), usize>, value: i32, left: usize, right: usize) -> usize {
+    let next_id = ids.len() + 1;
+    *ids.entry((value, left.min(right), left.max(right))).or_insert(next_id)
+}
+
+/**
+ * Canonical id of the subtree rooted at `node`, shared by every subtree equal to it up to child swaps
+ * `ids` interns (value, smaller child id, larger child id), id 0 stands for a missing child
+ */
+fn canonical_id(node: &Node, ids: &mut HashMap<(i32, usize, usize), usize>) -> usize {
+    let node_ids = fold_up(node.left.iter().chain(node.right.iter()).cloned(), |child, left, right| {
+        intern(ids, child.borrow().value, left.copied().unwrap_or(0), right.copied().unwrap_or(0))
+    });
+    let left = node_ids.of(node.left.as_ref()).copied().unwrap_or(0);
+    let right = node_ids.of(node.right.as_ref()).copied().unwrap_or(0);
+    intern(ids, node.value, left, right)
+}
+
+impl Node {
+    /**
+     * True if a node of this tree roots a subtree equal to `other`, values and shape
+     * The matched subtree must end where `other` ends, a bigger subtree starting the same way doesn't count
+     */
+    pub fn contains_subtree(&self, other: &Node) -> bool {
+        contains_run(&preorder_tokens(self), &preorder_tokens(other))
+    }
+
+    /**
+     * True if `other` can be turned into this tree by swapping the children of some nodes
+     */
+    pub fn is_isomorphic(&self, other: &Node) -> bool {
+        let mut ids: HashMap<(i32, usize, usize), usize> = HashMap::new();
+        canonical_id(self, &mut ids) == canonical_id(other, &mut ids)
+    }
+}
@@ -0,0 +1,54 @@
+//this package bridge tree::Node and BstNode trees
+//- Node::to_bst gathers the values of any binary tree into a balanced BstNode tree
+//- Node::from_bst copies a BstNode tree into tree::Node nodes, keeping its exact shape
+//both directions are iterative and build fresh nodes with their parent links, the source tree is left untouched
+//from_bst is a plain function: NodeLink and BstNodeLink are both Rc aliases, so `From<BstNodeLink> for NodeLink` is rejected by the orphan rule
+
+use crate::structure::bst::{BstNode, BstNodeLink};
+use crate::structure::tree::{Node, NodeLink};
+use std::rc::Rc;
+
+impl Node {
+    /**
+     * Build a balanced BstNode tree holding the values of the subtree rooted at self
+     * A value held by several nodes is kept once, since a BST can't hold duplicates
+     */
+    pub fn to_bst(&self) -> BstNodeLink {
+        let mut values: Vec<i32> = vec![self.value];
+        let mut stack: Vec<NodeLink> = self.left.iter().chain(self.right.iter()).cloned().collect();
+        while let Some(node) = stack.pop() {
+            let node_ref = node.borrow();
+            values.push(node_ref.value);
+            stack.extend(node_ref.left.clone());
+            stack.extend(node_ref.right.clone());
+        }
+        values.sort_unstable();
+        values.dedup();
+        BstNode::from_sorted(&values).unwrap()
+    }
+
+    /**
+     * Copy the BstNode tree rooted at `root` into tree::Node nodes with the same shape
+     * The keys become values, the copied root has no parent
+     */
+    pub fn from_bst(root: &BstNodeLink) -> NodeLink {
+        let new_root = Node::new_nodelink(root.borrow().key.unwrap());
+        //pairs of (original node, its copy) whose children are still to be copied
+        let mut stack: Vec<(BstNodeLink, NodeLink)> = vec![(Rc::clone(root), Rc::clone(&new_root))];
+
+        while let Some((original, copy)) = stack.pop() {
+            let original_ref = original.borrow();
+            if let Some(left) = &original_ref.left {
+                copy.borrow_mut().add_left_child(&copy, left.borrow().key.unwrap());
+                let left_copy = copy.borrow().left.clone().unwrap();
+                stack.push((Rc::clone(left), left_copy));
+            }
+            if let Some(right) = &original_ref.right {
+                copy.borrow_mut().add_right_child(&copy, right.borrow().key.unwrap());
+                let right_copy = copy.borrow().right.clone().unwrap();
+                stack.push((Rc::clone(right), right_copy));
+            }
+        }
+        new_root
+    }
+}
@@ -0,0 +1,243 @@
+//this package implement a text notation for BstNode and tree::Node trees, handy to write trees by hand
+//a node is its value, followed by its children in parentheses when it has any: 5(3(2,4),7(,10))
+//- either child may be left empty, 7(,10) has only a right child and 3(2,) only a left one
+//- 3(2) is accepted as a short form of 3(2,), the printer always writes the comma
+//- whitespace is ignored, an empty text is an empty tree
+//parsing and printing are iterative, parse errors carry the byte offset where the input went wrong
+
+use crate::structure::bst::{BstNode, BstNodeLink, KeyBounds};
+use crate::structure::tree::{Node, NodeLink};
+use std::fmt;
+use std::rc::Rc;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NotationError {
+    //byte offset in the input
+    pub position: usize,
+    pub message: String,
+}
+
+impl fmt::Display for NotationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at position {}", self.message, self.position)
+    }
+}
+
+impl std::error::Error for NotationError {}
+
+fn error<T>(position: usize, message: impl Into<String>) -> Result<T, NotationError> {
+    Err(NotationError {
+        position,
+        message: message.into(),
+    })
+}
+
+//one node in pre-order: its value, where it starts in the input, and its parent index with whether it is a left child
+struct ParsedNode {
+    value: i32,
+    position: usize,
+    parent: Option<(usize, bool)>,
+}
+
+fn skip_whitespace(text: &str, mut pos: usize) -> usize {
+    while text.as_bytes().get(pos).is_some_and(|byte| byte.is_ascii_whitespace()) {
+        pos += 1;
+    }
+    pos
+}
+
+fn parse_number(text: &str, pos: &mut usize) -> Result<i32, NotationError> {
+    let bytes = text.as_bytes();
+    let start = *pos;
+    let mut end = start;
+    if bytes.get(end) == Some(&b'-') {
+        end += 1;
+    }
+    let digits_start = end;
+    while bytes.get(end).is_some_and(|byte| byte.is_ascii_digit()) {
+        end += 1;
+    }
+    if end == digits_start {
+        return match text[start..].chars().next() {
+            Some(found) => error(start, format!("expected a number, found '{}'", found)),
+            None => error(start, "expected a number, found the end of input"),
+        };
+    }
+    match text[start..end].parse::<i32>() {
+        Ok(value) => {
+            *pos = end;
+            Ok(value)
+        }
+        Err(_) => error(start, format!("number {} is out of the i32 range", &text[start..end])),
+    }
+}
+
+fn parse(text: &str) -> Result<Vec<ParsedNode>, NotationError> {
+    let mut nodes: Vec<ParsedNode> = Vec::new();
+    //nodes whose parentheses are open, and whether their right slot was reached
+    let mut open: Vec<(usize, bool)> = Vec::new();
+    let mut pos = skip_whitespace(text, 0);
+    if pos == text.len() {
+        return Ok(nodes);
+    }
+    //a child slot (or the root) starts here
+    let mut expect_value = true;
+    //the last thing read was a value, so its children may follow
+    let mut can_open = false;
+
+    loop {
+        pos = skip_whitespace(text, pos);
+        let next = text.as_bytes().get(pos).copied();
+        if expect_value {
+            expect_value = false;
+            can_open = false;
+            //only the root is mandatory, a child slot may be empty
+            if !open.is_empty() && matches!(next, Some(b',') | Some(b')')) {
+                continue;
+            }
+            let position = pos;
+            let value = parse_number(text, &mut pos)?;
+            let parent = open.last().map(|&(index, right)| (index, !right));
+            nodes.push(ParsedNode { value, position, parent });
+            can_open = true;
+            continue;
+        }
+
+        match next {
+            None if open.is_empty() => return Ok(nodes),
+            None => return error(pos, format!("{} unclosed '('", open.len())),
+            Some(b'(') if can_open => {
+                open.push((nodes.len() - 1, false));
+                expect_value = true;
+            }
+            Some(b',') => match open.last_mut() {
+                Some((_, right)) if !*right => {
+                    *right = true;
+                    expect_value = true;
+                }
+                Some(_) => return error(pos, "a node has at most two children"),
+                None => return error(pos, "unexpected ',' outside of parentheses"),
+            },
+            Some(b')') => {
+                if open.pop().is_none() {
+                    return error(pos, "unexpected ')' without a matching '('");
+                }
+                can_open = false;
+            }
+            Some(_) => {
+                let found = text[pos..].chars().next().unwrap();
+                return error(pos, format!("unexpected '{}'", found));
+            }
+        }
+        pos += 1;
+    }
+}
+
+//what the printer needs from a node: its value and its children
+type NodeParts<T> = (i32, Option<T>, Option<T>);
+
+enum PrintStep<T> {
+    Node(T),
+    Text(&'static str),
+}
+
+fn print<T>(root: Option<T>, parts: impl Fn(&T) -> NodeParts<T>) -> String {
+    let mut out = String::new();
+    let mut stack: Vec<PrintStep<T>> = root.into_iter().map(PrintStep::Node).collect();
+    while let Some(step) = stack.pop() {
+        match step {
+            PrintStep::Text(text) => out.push_str(text),
+            PrintStep::Node(node) => {
+                let (value, left, right) = parts(&node);
+                out.push_str(&value.to_string());
+                if left.is_none() && right.is_none() {
+                    continue;
+                }
+                //pushed in reverse, "(" comes out first
+                stack.push(PrintStep::Text(")"));
+                stack.extend(right.map(PrintStep::Node));
+                stack.push(PrintStep::Text(","));
+                stack.extend(left.map(PrintStep::Node));
+                stack.push(PrintStep::Text("("));
+            }
+        }
+    }
+    out
+}
+
+impl Node {
+    /**
+     * Parse a tree written like 5(3(2,4),7(,10)), rebuilding the parent links
+     * Return None for an empty text
+     */
+    pub fn from_notation(text: &str) -> Result<Option<NodeLink>, NotationError> {
+        let nodes = parse(text)?;
+        let mut built: Vec<NodeLink> = Vec::with_capacity(nodes.len());
+        for node in &nodes {
+            let link = match node.parent {
+                None => Node::new_nodelink(node.value),
+                Some((parent_index, is_left)) => {
+                    let parent = &built[parent_index];
+                    if is_left {
+                        parent.borrow_mut().add_left_child(parent, node.value);
+                        parent.borrow().left.clone().unwrap()
+                    } else {
+                        parent.borrow_mut().add_right_child(parent, node.value);
+                        parent.borrow().right.clone().unwrap()
+                    }
+                }
+            };
+            built.push(link);
+        }
+        Ok(built.first().map(Rc::clone))
+    }
+
+    /**
+     * Write the tree rooted at `root` in the notation read by from_notation, None writes an empty text
+     */
+    pub fn to_notation(root: Option<&NodeLink>) -> String {
+        print(root.cloned(), |node| {
+            let node_ref = node.borrow();
+            (node_ref.value, node_ref.left.clone(), node_ref.right.clone())
+        })
+    }
+}
+
+impl BstNode {
+    /**
+     * Parse a tree written like 5(3(2,4),7(,10)), rebuilding the parent links
+     * Keys breaking the BST ordering are rejected with their position
+     * Return None for an empty text
+     */
+    pub fn from_notation(text: &str) -> Result<Option<BstNodeLink>, NotationError> {
+        let nodes = parse(text)?;
+        let mut built: Vec<BstNodeLink> = Vec::with_capacity(nodes.len());
+        //exclusive key bounds of every built node
+        let mut bounds: Vec<KeyBounds> = Vec::with_capacity(nodes.len());
+        for node in &nodes {
+            let Some((parent_index, is_left)) = node.parent else {
+                built.push(BstNode::new_bst_nodelink(node.value));
+                bounds.push((None, None));
+                continue;
+            };
+            match BstNode::attach_checked(&built[parent_index], bounds[parent_index], is_left, node.value) {
+                Ok((child, child_bounds)) => {
+                    built.push(child);
+                    bounds.push(child_bounds);
+                }
+                Err(message) => return error(node.position, message),
+            }
+        }
+        Ok(built.first().map(Rc::clone))
+    }
+
+    /**
+     * Write the tree rooted at `root` in the notation read by from_notation, None writes an empty text
+     */
+    pub fn to_notation(root: Option<&BstNodeLink>) -> String {
+        print(root.cloned(), |node| {
+            let node_ref = node.borrow();
+            (node_ref.key.unwrap(), node_ref.left.clone(), node_ref.right.clone())
+        })
+    }
+}
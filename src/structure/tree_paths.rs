@@ -0,0 +1,88 @@
+//this package enumerate paths of tree::Node trees, every path is given as the real node handles
+//sums are computed in i64, so long paths of large values don't overflow
+//every walk is iterative
+
+use crate::structure::tree::{Node, NodeLink};
+use crate::structure::tree_walk::fold_up;
+use std::rc::Rc;
+
+fn path_sum(path: &[NodeLink]) -> i64 {
+    path.iter().map(|node| node.borrow().value as i64).sum()
+}
+
+impl Node {
+    /**
+     * Every path from `root` down to a leaf, from the leftmost leaf to the rightmost one
+     * Paths are built going down from `root`, so only child links are followed, never parent links
+     */
+    pub fn root_to_leaf_paths(root: &NodeLink) -> Vec<Vec<NodeLink>> {
+        let mut paths: Vec<Vec<NodeLink>> = Vec::new();
+        //the path down to the node being visited, cut back to its depth before it is extended
+        let mut path: Vec<NodeLink> = Vec::new();
+        let mut stack: Vec<(NodeLink, usize)> = vec![(Rc::clone(root), 0)];
+        while let Some((node, depth)) = stack.pop() {
+            path.truncate(depth);
+            path.push(Rc::clone(&node));
+            let node_ref = node.borrow();
+            if node_ref.left.is_none() && node_ref.right.is_none() {
+                paths.push(path.clone());
+                continue;
+            }
+            //right first so the left subtree comes out first
+            stack.extend(node_ref.right.clone().map(|right| (right, depth + 1)));
+            stack.extend(node_ref.left.clone().map(|left| (left, depth + 1)));
+        }
+        paths
+    }
+
+    /**
+     * The root to leaf paths whose values add up to `target`
+     */
+    pub fn paths_with_sum(root: &NodeLink, target: i64) -> Vec<Vec<NodeLink>> {
+        Node::root_to_leaf_paths(root)
+            .into_iter()
+            .filter(|path| path_sum(path) == target)
+            .collect()
+    }
+
+    /**
+     * The path between any two nodes (or a single node) with the largest sum, with that sum
+     * The path is given from one end to the other, going up to its highest node then down
+     */
+    pub fn max_path_sum(root: &NodeLink) -> (i64, Vec<NodeLink>) {
+        //best sum of a path going down from a node, and the child it continues through if any
+        let mut best: Option<(i64, NodeLink)> = None;
+        let gains = fold_up([Rc::clone(root)], |node, left, right| {
+            let node_ref = node.borrow();
+            //a child only helps when its downward path adds something
+            let gain_of = |child: Option<&(i64, Option<NodeLink>)>| child.map_or(0, |(gain, _)| (*gain).max(0));
+            let (left_gain, right_gain) = (gain_of(left), gain_of(right));
+            let value = node_ref.value as i64;
+
+            let through = value + left_gain + right_gain;
+            if best.as_ref().is_none_or(|(sum, _)| through > *sum) {
+                best = Some((through, Rc::clone(node)));
+            }
+            let next = if left_gain >= right_gain { &node_ref.left } else { &node_ref.right };
+            let next = next.clone().filter(|_| left_gain.max(right_gain) > 0);
+            (value + left_gain.max(right_gain), next)
+        });
+
+        let (sum, top) = best.unwrap();
+        //follow the best downward path of a child of the top node, if it adds something
+        let descend = |child: Option<NodeLink>| {
+            let mut path: Vec<NodeLink> = Vec::new();
+            let mut current = child.filter(|child| gains.get(child).0 > 0);
+            while let Some(node) = current {
+                current = gains.get(&node).1.clone();
+                path.push(node);
+            }
+            path
+        };
+        let mut path = descend(top.borrow().left.clone());
+        path.reverse();
+        path.push(Rc::clone(&top));
+        path.extend(descend(top.borrow().right.clone()));
+        (sum, path)
+    }
+}
@@ -0,0 +1,173 @@
+//this package rebuild a tree::Node tree from its traversal sequences
+//- pre-order + in-order, or post-order + in-order: the tree is unique as long as the values are distinct
+//- pre-order alone: only for a BST, the ordering of the keys tells where each node goes
+//every construction is iterative and sets the parent links
+
+use crate::structure::tree::{Node, NodeLink};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::rc::Rc;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TraversalError {
+    //both sequences must list every node once
+    LengthMismatch { order: usize, inorder: usize },
+    //a value shows up twice, so more than one tree fits the sequences
+    DuplicateValue(i32),
+    //the value is missing from the in-order sequence or sits on the wrong side of its ancestor
+    Inconsistent(i32),
+    //the pre-order sequence can't come from a BST, `value` at `index` breaks the ordering
+    NotBstPreorder { index: usize, value: i32 },
+}
+
+impl fmt::Display for TraversalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TraversalError::LengthMismatch { order, inorder } => write!(
+                f,
+                "sequences differ in length, {} against {} in-order values",
+                order, inorder
+            ),
+            TraversalError::DuplicateValue(value) => {
+                write!(f, "value {} appears more than once, the tree is ambiguous", value)
+            }
+            TraversalError::Inconsistent(value) => {
+                write!(f, "value {} doesn't fit the in-order sequence", value)
+            }
+            TraversalError::NotBstPreorder { index, value } => {
+                write!(f, "value {} at index {} breaks the BST pre-order", value, index)
+            }
+        }
+    }
+}
+
+impl std::error::Error for TraversalError {}
+
+fn check_distinct(values: &[i32]) -> Result<(), TraversalError> {
+    let mut seen: HashSet<i32> = HashSet::new();
+    match values.iter().find(|value| !seen.insert(**value)) {
+        Some(value) => Err(TraversalError::DuplicateValue(*value)),
+        None => Ok(()),
+    }
+}
+
+//a subtree still to be built: the parent and side to attach it to (None for the root), and its in-order range [low, high)
+type PendingSubtree = (Option<(NodeLink, bool)>, usize, usize);
+
+fn attach(parent: &NodeLink, is_left: bool, value: i32) -> NodeLink {
+    if is_left {
+        parent.borrow_mut().add_left_child(parent, value);
+        parent.borrow().left.clone().unwrap()
+    } else {
+        parent.borrow_mut().add_right_child(parent, value);
+        parent.borrow().right.clone().unwrap()
+    }
+}
+
+/**
+ * Shared by both pairs: `order` is read from the front for pre-order (node, left, right)
+ * and from the back for post-order, which then reads as (node, right, left)
+ */
+fn rebuild(order: &[i32], inorder: &[i32], postorder: bool) -> Result<Option<NodeLink>, TraversalError> {
+    if order.len() != inorder.len() {
+        return Err(TraversalError::LengthMismatch {
+            order: order.len(),
+            inorder: inorder.len(),
+        });
+    }
+    check_distinct(order)?;
+    check_distinct(inorder)?;
+    let position: HashMap<i32, usize> = inorder.iter().enumerate().map(|(index, value)| (*value, index)).collect();
+
+    let mut read: usize = 0;
+    let mut root: Option<NodeLink> = None;
+    let mut stack: Vec<PendingSubtree> = vec![(None, 0, inorder.len())];
+
+    while let Some((slot, low, high)) = stack.pop() {
+        if low == high {
+            continue;
+        }
+        //a non-empty range always has a value left, the lengths match
+        let value = if postorder { order[order.len() - 1 - read] } else { order[read] };
+        read += 1;
+        let index = match position.get(&value) {
+            Some(index) if (low..high).contains(index) => *index,
+            _ => return Err(TraversalError::Inconsistent(value)),
+        };
+        let node = match slot {
+            None => {
+                let node = Node::new_nodelink(value);
+                root = Some(Rc::clone(&node));
+                node
+            }
+            Some((parent, is_left)) => attach(&parent, is_left, value),
+        };
+
+        let left = (Some((Rc::clone(&node), true)), low, index);
+        let right = (Some((node, false)), index + 1, high);
+        //the subtree read next goes on top
+        if postorder {
+            stack.push(left);
+            stack.push(right);
+        } else {
+            stack.push(right);
+            stack.push(left);
+        }
+    }
+    Ok(root)
+}
+
+impl Node {
+    /**
+     * Rebuild the unique tree with the given pre-order and in-order sequences
+     * Return None for empty sequences
+     */
+    pub fn from_preorder_inorder(preorder: &[i32], inorder: &[i32]) -> Result<Option<NodeLink>, TraversalError> {
+        rebuild(preorder, inorder, false)
+    }
+
+    /**
+     * Rebuild the unique tree with the given post-order and in-order sequences
+     * Return None for empty sequences
+     */
+    pub fn from_postorder_inorder(postorder: &[i32], inorder: &[i32]) -> Result<Option<NodeLink>, TraversalError> {
+        rebuild(postorder, inorder, true)
+    }
+
+    /**
+     * Rebuild a BST from its pre-order alone, smaller keys go left
+     * Return None for an empty sequence
+     */
+    pub fn from_bst_preorder(preorder: &[i32]) -> Result<Option<NodeLink>, TraversalError> {
+        check_distinct(preorder)?;
+        let Some(first) = preorder.first() else {
+            return Ok(None);
+        };
+        let root = Node::new_nodelink(*first);
+        //nodes still able to take a right child, values decreasing towards the top
+        let mut stack: Vec<NodeLink> = vec![Rc::clone(&root)];
+        //once we went right of a node, every later key must be greater than it
+        let mut lower: Option<i32> = None;
+
+        for (index, value) in preorder.iter().enumerate().skip(1) {
+            if lower.is_some_and(|bound| *value < bound) {
+                return Err(TraversalError::NotBstPreorder { index, value: *value });
+            }
+            //climb to the last ancestor smaller than value, value becomes its right child
+            let mut right_of: Option<NodeLink> = None;
+            while stack.last().is_some_and(|top| top.borrow().value < *value) {
+                right_of = stack.pop();
+            }
+            let node = match right_of {
+                Some(parent) => {
+                    lower = Some(parent.borrow().value);
+                    attach(&parent, false, *value)
+                }
+                //the top is greater than value and has no left child yet
+                None => attach(stack.last().unwrap(), true, *value),
+            };
+            stack.push(node);
+        }
+        Ok(Some(root))
+    }
+}
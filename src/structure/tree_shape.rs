@@ -0,0 +1,153 @@
+//this package implement the classic shape algorithms on tree::Node trees
+//mirror, diameter, balance, completeness, fullness, perfection, width and leaves
+//every walk uses an explicit stack or queue, so degenerate trees with many levels don't overflow the call stack
+
+use crate::structure::tree::{Node, NodeLink};
+use crate::structure::tree_walk::fold_up;
+use std::collections::VecDeque;
+use std::rc::Rc;
+
+impl Node {
+    /**
+     * Height (in nodes, an empty tree is 0) of the subtree rooted at self, computed bottom-up
+     * `visit` is called once per node with the heights of its left and right subtrees
+     */
+    fn fold_heights(&self, visit: &mut impl FnMut(usize, usize)) -> usize {
+        let mut height = |left: Option<&usize>, right: Option<&usize>| {
+            let (left, right) = (left.copied().unwrap_or(0), right.copied().unwrap_or(0));
+            visit(left, right);
+            left.max(right) + 1
+        };
+        let heights = fold_up(self.left.iter().chain(self.right.iter()).cloned(), |_, left, right| {
+            height(left, right)
+        });
+        height(heights.of(self.left.as_ref()), heights.of(self.right.as_ref()))
+    }
+
+    //amount of nodes on every level of the subtree rooted at self, the first level holds self
+    fn level_widths(&self) -> Vec<usize> {
+        let mut widths: Vec<usize> = vec![1];
+        let mut level: Vec<NodeLink> = self.left.iter().chain(self.right.iter()).cloned().collect();
+        while !level.is_empty() {
+            widths.push(level.len());
+            level = level
+                .iter()
+                .flat_map(|node| {
+                    let node_ref = node.borrow();
+                    [node_ref.left.clone(), node_ref.right.clone()]
+                })
+                .flatten()
+                .collect();
+        }
+        widths
+    }
+
+    /**
+     * Swap the children of every node of the subtree rooted at self
+     * A node keeps the same parent once mirrored, so the parent links stay valid as they are
+     */
+    pub fn mirror(&mut self) {
+        std::mem::swap(&mut self.left, &mut self.right);
+        let mut stack: Vec<NodeLink> = self.left.iter().chain(self.right.iter()).cloned().collect();
+        while let Some(node) = stack.pop() {
+            let mut node_mut = node.borrow_mut();
+            let node_mut = &mut *node_mut;
+            std::mem::swap(&mut node_mut.left, &mut node_mut.right);
+            stack.extend(node_mut.left.clone());
+            stack.extend(node_mut.right.clone());
+        }
+    }
+
+    /**
+     * Number of edges on the longest path between two nodes of the subtree rooted at self
+     */
+    pub fn diameter(&self) -> usize {
+        let mut diameter = 0;
+        self.fold_heights(&mut |left, right| diameter = diameter.max(left + right));
+        diameter
+    }
+
+    /**
+     * True if the heights of the two subtrees of every node differ by one at most
+     */
+    pub fn is_balanced(&self) -> bool {
+        let mut balanced = true;
+        self.fold_heights(&mut |left, right| balanced &= left.abs_diff(right) <= 1);
+        balanced
+    }
+
+    /**
+     * True if every level is filled, except maybe the last one whose nodes are all to the left
+     */
+    pub fn is_complete(&self) -> bool {
+        //children slots in level order, a node found after an empty slot breaks completeness
+        let mut queue: VecDeque<Option<NodeLink>> = VecDeque::from([self.left.clone(), self.right.clone()]);
+        let mut seen_gap = false;
+        while let Some(slot) = queue.pop_front() {
+            match slot {
+                None => seen_gap = true,
+                Some(_) if seen_gap => return false,
+                Some(node) => {
+                    queue.push_back(node.borrow().left.clone());
+                    queue.push_back(node.borrow().right.clone());
+                }
+            }
+        }
+        true
+    }
+
+    /**
+     * True if every node has either no child or two children
+     */
+    pub fn is_full(&self) -> bool {
+        if self.left.is_some() != self.right.is_some() {
+            return false;
+        }
+        let mut stack: Vec<NodeLink> = self.left.iter().chain(self.right.iter()).cloned().collect();
+        while let Some(node) = stack.pop() {
+            let node_ref = node.borrow();
+            if node_ref.left.is_some() != node_ref.right.is_some() {
+                return false;
+            }
+            stack.extend(node_ref.left.clone());
+            stack.extend(node_ref.right.clone());
+        }
+        true
+    }
+
+    /**
+     * True if every level is completely filled, i.e. level k holds 2^k nodes
+     */
+    pub fn is_perfect(&self) -> bool {
+        self.level_widths()
+            .iter()
+            .enumerate()
+            .all(|(level, width)| *width == 1 << level)
+    }
+
+    /**
+     * The largest amount of nodes found on a single level
+     */
+    pub fn max_width(&self) -> usize {
+        self.level_widths().into_iter().max().unwrap()
+    }
+
+    /**
+     * Every node without children in the tree rooted at `root`, from left to right
+     */
+    pub fn leaves(root: &NodeLink) -> Vec<NodeLink> {
+        let mut leaves: Vec<NodeLink> = Vec::new();
+        let mut stack: Vec<NodeLink> = vec![Rc::clone(root)];
+        while let Some(node) = stack.pop() {
+            let node_ref = node.borrow();
+            if node_ref.left.is_none() && node_ref.right.is_none() {
+                drop(node_ref);
+                leaves.push(node);
+                continue;
+            }
+            stack.extend(node_ref.right.clone());
+            stack.extend(node_ref.left.clone());
+        }
+        leaves
+    }
+}
@@ -0,0 +1,163 @@
+//this package implement view oriented traversals of tree::Node trees, the way a UI lays the tree out
+//every yielded node carries its level (the root is level 0) and its column, the horizontal distance
+//from the root: a left child sits one column left of its parent, a right child one column right
+//- vertical order: column by column from the left, top to bottom inside a column
+//- boundary: the root, the left edge down, the leaves from left to right, then the right edge back up
+//- zigzag: level order, every other level read from right to left
+//- left and right side views: the first and last node of every level
+//level based traversals are lazy, one level is kept in memory at a time
+
+use crate::structure::tree::{Node, NodeLink};
+use std::rc::Rc;
+
+#[derive(Debug, Clone)]
+pub struct PositionedNode {
+    pub node: NodeLink,
+    pub level: usize,
+    pub column: i32,
+}
+
+impl PositionedNode {
+    fn root(node: &NodeLink) -> Self {
+        PositionedNode {
+            node: Rc::clone(node),
+            level: 0,
+            column: 0,
+        }
+    }
+
+    fn left(&self) -> Option<PositionedNode> {
+        self.node.borrow().left.clone().map(|node| PositionedNode {
+            node,
+            level: self.level + 1,
+            column: self.column - 1,
+        })
+    }
+
+    fn right(&self) -> Option<PositionedNode> {
+        self.node.borrow().right.clone().map(|node| PositionedNode {
+            node,
+            level: self.level + 1,
+            column: self.column + 1,
+        })
+    }
+
+    fn is_leaf(&self) -> bool {
+        let node_ref = self.node.borrow();
+        node_ref.left.is_none() && node_ref.right.is_none()
+    }
+}
+
+//the levels of a tree from the top, each one from left to right
+struct Levels {
+    next: Vec<PositionedNode>,
+}
+
+impl Iterator for Levels {
+    type Item = Vec<PositionedNode>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next.is_empty() {
+            return None;
+        }
+        let below: Vec<PositionedNode> = self
+            .next
+            .iter()
+            .flat_map(|positioned| [positioned.left(), positioned.right()])
+            .flatten()
+            .collect();
+        Some(std::mem::replace(&mut self.next, below))
+    }
+}
+
+fn levels(root: &NodeLink) -> Levels {
+    Levels {
+        next: vec![PositionedNode::root(root)],
+    }
+}
+
+/**
+ * Follow one edge of the tree below the root, preferring the left (or right) child and falling back to the other one
+ * Leaves are left out, they are yielded with the other leaves
+ */
+fn edge(root: &PositionedNode, prefer_left: bool) -> Vec<PositionedNode> {
+    let step = |positioned: &PositionedNode| {
+        if prefer_left {
+            positioned.left().or_else(|| positioned.right())
+        } else {
+            positioned.right().or_else(|| positioned.left())
+        }
+    };
+    let first = if prefer_left { root.left() } else { root.right() };
+    std::iter::successors(first, step)
+        .filter(|positioned| !positioned.is_leaf())
+        .collect()
+}
+
+impl Node {
+    /**
+     * Every node of the tree rooted at `root`, column by column from the leftmost one
+     * Inside a column the nodes come by level, then from left to right
+     */
+    pub fn vertical_order(root: &NodeLink) -> impl Iterator<Item = PositionedNode> {
+        let mut nodes: Vec<PositionedNode> = levels(root).flatten().collect();
+        //stable, so the level order is kept inside a column
+        nodes.sort_by_key(|positioned| positioned.column);
+        nodes.into_iter()
+    }
+
+    /**
+     * The outline of the tree rooted at `root`, anti-clockwise from the root
+     * Each node shows up once, even when it lies on an edge and is a leaf too
+     */
+    pub fn boundary(root: &NodeLink) -> impl Iterator<Item = PositionedNode> {
+        let top = PositionedNode::root(root);
+        let mut outline: Vec<PositionedNode> = Vec::new();
+        if !top.is_leaf() {
+            outline.push(top.clone());
+            outline.extend(edge(&top, true));
+        }
+
+        //leaves from left to right (Iterative version)
+        let mut stack: Vec<PositionedNode> = vec![top.clone()];
+        while let Some(positioned) = stack.pop() {
+            if positioned.is_leaf() {
+                outline.push(positioned);
+                continue;
+            }
+            stack.extend(positioned.right());
+            stack.extend(positioned.left());
+        }
+
+        let mut right_edge = edge(&top, false);
+        right_edge.reverse();
+        outline.extend(right_edge);
+        outline.into_iter()
+    }
+
+    /**
+     * Level order of the tree rooted at `root`, levels 1, 3, 5, ... read from right to left
+     */
+    pub fn zigzag_level_order(root: &NodeLink) -> impl Iterator<Item = PositionedNode> {
+        levels(root).flat_map(|mut level| {
+            if level[0].level % 2 == 1 {
+                level.reverse();
+            }
+            level
+        })
+    }
+
+    /**
+     * The node seen first on every level when looking at the tree from the left
+     */
+    pub fn left_side_view(root: &NodeLink) -> impl Iterator<Item = PositionedNode> {
+        levels(root).map(|level| level.into_iter().next().unwrap())
+    }
+
+    /**
+     * The node seen first on every level when looking at the tree from the right
+     */
+    pub fn right_side_view(root: &NodeLink) -> impl Iterator<Item = PositionedNode> {
+        levels(root).map(|mut level| level.pop().unwrap())
+    }
+}
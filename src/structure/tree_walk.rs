@@ -0,0 +1,47 @@
+//this package implement walks shared by the tree_* modules over tree::Node trees
+//they use an explicit stack, so degenerate trees with many levels don't overflow the call stack
+
+use crate::structure::tree::{Node, NodeLink};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+//what fold_up computed for every node, looked up by node
+pub(crate) struct Folded<T>(HashMap<*const RefCell<Node>, T>);
+
+impl<T> Folded<T> {
+    pub(crate) fn get(&self, node: &NodeLink) -> &T {
+        &self.0[&Rc::as_ptr(node)]
+    }
+
+    //None for a missing child
+    pub(crate) fn of(&self, child: Option<&NodeLink>) -> Option<&T> {
+        child.map(|child| self.get(child))
+    }
+}
+
+/**
+ * Fold the subtrees rooted at `roots` bottom-up, children before their parent
+ * `visit` is called once per node with what it returned for the left and right children
+ */
+pub(crate) fn fold_up<T>(
+    roots: impl IntoIterator<Item = NodeLink>,
+    mut visit: impl FnMut(&NodeLink, Option<&T>, Option<&T>) -> T,
+) -> Folded<T> {
+    //pre-order, so every child comes after its parent and the reverse visits children first
+    let mut order: Vec<NodeLink> = Vec::new();
+    let mut stack: Vec<NodeLink> = roots.into_iter().collect();
+    while let Some(node) = stack.pop() {
+        stack.extend(node.borrow().left.clone());
+        stack.extend(node.borrow().right.clone());
+        order.push(node);
+    }
+
+    let mut folded = Folded(HashMap::new());
+    for node in order.iter().rev() {
+        let node_ref = node.borrow();
+        let result = visit(node, folded.of(node_ref.left.as_ref()), folded.of(node_ref.right.as_ref()));
+        folded.0.insert(Rc::as_ptr(node), result);
+    }
+    folded
+}
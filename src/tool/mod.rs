@@ -30,17 +30,17 @@ fn node_traversal(node: &NodeLink) -> String{
     //we print the child nodes first
     let left_child = &node.borrow().left;
     //won't print anything if left child is None
-    new_info += &print_child(&node, left_child.as_ref());
+    new_info += &print_child(node, left_child.as_ref());
     let right_child = &node.borrow().right;
-    new_info += &print_child(&node, right_child.as_ref());
+    new_info += &print_child(node, right_child.as_ref());
     //now we need to traverse deeper
     if left_child.is_some(){
-        new_info += &node_traversal(&left_child.as_ref().unwrap());
+        new_info += &node_traversal(left_child.as_ref().unwrap());
     }
     if right_child.is_some(){
-        new_info += &node_traversal(&right_child.as_ref().unwrap());
+        new_info += &node_traversal(right_child.as_ref().unwrap());
     }
-    return new_info;
+    new_info
 }
 
 fn print_child(parent_node: &NodeLink, child_node: Option<&NodeLink>) -> String{
@@ -54,7 +54,7 @@ fn print_child(parent_node: &NodeLink, child_node: Option<&NodeLink>) -> String{
         new_info += &child.borrow().value.to_string();
         new_info += ";\n";
     }
-    return new_info;
+    new_info
 }
 
 pub fn generate_dotfile_bst(root: &BstNodeLink, output_path: &str){
@@ -73,17 +73,17 @@ fn node_traversal_bst(node: &BstNodeLink) -> String{
     //we print the child nodes first
     let left_child = &node.borrow().left;
     //won't print anything if left child is None
-    new_info += &print_child_bst(&node, left_child.as_ref());
+    new_info += &print_child_bst(node, left_child.as_ref());
     let right_child = &node.borrow().right;
-    new_info += &print_child_bst(&node, right_child.as_ref());
+    new_info += &print_child_bst(node, right_child.as_ref());
     //now we need to traverse deeper
     if left_child.is_some(){
-        new_info += &node_traversal_bst(&left_child.as_ref().unwrap());
+        new_info += &node_traversal_bst(left_child.as_ref().unwrap());
     }
     if right_child.is_some(){
-        new_info += &node_traversal_bst(&right_child.as_ref().unwrap());
+        new_info += &node_traversal_bst(right_child.as_ref().unwrap());
     }
-    return new_info;
+    new_info
 }
 
 fn print_child_bst(parent_node: &BstNodeLink, child_node: Option<&BstNodeLink>) -> String{
@@ -97,7 +97,7 @@ fn print_child_bst(parent_node: &BstNodeLink, child_node: Option<&BstNodeLink>)
         new_info += &child.borrow().key.unwrap().to_string();
         new_info += ";\n";
     }
-    return new_info;
+    new_info
 }
 
 /*